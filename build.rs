@@ -0,0 +1,43 @@
+use std::{env, process::Command, str};
+
+// Detect features of the compiler that bitflags can optionally use
+//
+// This only runs `rustc --version`, so it doesn't need any dependencies
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let minor = match rustc_minor_version() {
+        Some(minor) => minor,
+        None => return,
+    };
+
+    // Cargo only understands `rustc-check-cfg` in 1.80+
+    if minor >= 80 {
+        println!("cargo:rustc-check-cfg=cfg(bitflags_core_error)");
+        println!("cargo:rustc-check-cfg=cfg(bitflags_const_from_utf8)");
+    }
+
+    // `core::str::from_utf8` was made const in 1.63
+    if minor >= 63 {
+        println!("cargo:rustc-cfg=bitflags_const_from_utf8");
+    }
+
+    // `core::error::Error` was stabilized in 1.81
+    if minor >= 81 {
+        println!("cargo:rustc-cfg=bitflags_core_error");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = str::from_utf8(&output.stdout).ok()?;
+
+    // The version looks like `rustc 1.56.0 (09c42c458 2021-10-18)`
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+
+    pieces.next()?.parse().ok()
+}
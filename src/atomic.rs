@@ -0,0 +1,345 @@
+/*!
+Share flags values between threads using atomic operations.
+
+The [`Atomic`] type wraps the atomic integer matching a flags type's bits type, so flags can be
+inserted, removed, and toggled without a lock:
+
+```
+use std::sync::atomic::Ordering;
+
+use bitflags::{atomic::Atomic, bitflags};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct State: u32 {
+        const READY = 1;
+        const BUSY = 1 << 1;
+    }
+}
+
+let state = Atomic::new(State::READY);
+
+let before = state.fetch_insert(State::BUSY, Ordering::AcqRel);
+assert_eq!(State::READY, before);
+
+state.fetch_remove(State::READY, Ordering::AcqRel);
+assert_eq!(State::BUSY, state.load(Ordering::Acquire));
+```
+
+This module requires the `atomic` feature, and Rust 1.60 or later. Bits types are only
+supported on targets that provide atomic operations for their width.
+*/
+
+use core::{fmt, marker::PhantomData, sync::atomic::Ordering};
+
+use crate::{Bits, Flags};
+
+/**
+A bits type that has a corresponding atomic integer type.
+*/
+pub trait AtomicBits: Bits {
+    /// The atomic integer type used to store values of this bits type.
+    type Atomic: Send + Sync;
+
+    /// Create a new atomic value.
+    fn new_atomic(bits: Self) -> Self::Atomic;
+
+    /// Consume the atomic value, returning the bits it contains.
+    fn into_inner(atomic: Self::Atomic) -> Self;
+
+    /// Load the bits from the atomic value.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Store the bits into the atomic value.
+    fn store(atomic: &Self::Atomic, bits: Self, order: Ordering);
+
+    /// Store the bits into the atomic value, returning the previous bits.
+    fn swap(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self;
+
+    /// The bitwise or (`|`) of the atomic value with the bits, returning the previous bits.
+    fn fetch_or(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self;
+
+    /// The bitwise and (`&`) of the atomic value with the bits, returning the previous bits.
+    fn fetch_and(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self;
+
+    /// The bitwise exclusive-or (`^`) of the atomic value with the bits, returning the previous bits.
+    fn fetch_xor(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self;
+
+    /// Store `new` into the atomic value if it currently contains `current`.
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+
+    /// Store `new` into the atomic value if it currently contains `current`.
+    ///
+    /// This method may fail spuriously, even when the comparison succeeds.
+    fn compare_exchange_weak(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+}
+
+macro_rules! impl_atomic_bits {
+    ($($width:literal => $($ty:ty: $atomic:ident),*;)*) => {
+        $(
+            $(
+                #[cfg(target_has_atomic = $width)]
+                impl AtomicBits for $ty {
+                    type Atomic = core::sync::atomic::$atomic;
+
+                    #[inline]
+                    fn new_atomic(bits: Self) -> Self::Atomic {
+                        core::sync::atomic::$atomic::new(bits)
+                    }
+
+                    #[inline]
+                    fn into_inner(atomic: Self::Atomic) -> Self {
+                        atomic.into_inner()
+                    }
+
+                    #[inline]
+                    fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                        atomic.load(order)
+                    }
+
+                    #[inline]
+                    fn store(atomic: &Self::Atomic, bits: Self, order: Ordering) {
+                        atomic.store(bits, order)
+                    }
+
+                    #[inline]
+                    fn swap(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self {
+                        atomic.swap(bits, order)
+                    }
+
+                    #[inline]
+                    fn fetch_or(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self {
+                        atomic.fetch_or(bits, order)
+                    }
+
+                    #[inline]
+                    fn fetch_and(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self {
+                        atomic.fetch_and(bits, order)
+                    }
+
+                    #[inline]
+                    fn fetch_xor(atomic: &Self::Atomic, bits: Self, order: Ordering) -> Self {
+                        atomic.fetch_xor(bits, order)
+                    }
+
+                    #[inline]
+                    fn compare_exchange(
+                        atomic: &Self::Atomic,
+                        current: Self,
+                        new: Self,
+                        success: Ordering,
+                        failure: Ordering,
+                    ) -> Result<Self, Self> {
+                        atomic.compare_exchange(current, new, success, failure)
+                    }
+
+                    #[inline]
+                    fn compare_exchange_weak(
+                        atomic: &Self::Atomic,
+                        current: Self,
+                        new: Self,
+                        success: Ordering,
+                        failure: Ordering,
+                    ) -> Result<Self, Self> {
+                        atomic.compare_exchange_weak(current, new, success, failure)
+                    }
+                }
+            )*
+        )*
+    };
+}
+
+impl_atomic_bits! {
+    "8" => u8: AtomicU8, i8: AtomicI8;
+    "16" => u16: AtomicU16, i16: AtomicI16;
+    "32" => u32: AtomicU32, i32: AtomicI32;
+    "64" => u64: AtomicU64, i64: AtomicI64;
+    "ptr" => usize: AtomicUsize, isize: AtomicIsize;
+}
+
+/**
+A flags value that can be shared between threads.
+
+Operations take and return flags values, and are mapped onto the atomic integer
+for the flags type's [`Flags::Bits`].
+*/
+pub struct Atomic<F: Flags>
+where
+    F::Bits: AtomicBits,
+{
+    bits: <F::Bits as AtomicBits>::Atomic,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Flags> Atomic<F>
+where
+    F::Bits: AtomicBits,
+{
+    /// Create a new atomic flags value.
+    #[inline]
+    pub fn new(flags: F) -> Self {
+        Atomic {
+            bits: F::Bits::new_atomic(flags.bits()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Consume the atomic flags value, returning the flags it contains.
+    #[inline]
+    pub fn into_inner(self) -> F {
+        F::from_bits_retain(F::Bits::into_inner(self.bits))
+    }
+
+    /// Load the flags value.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> F {
+        F::from_bits_retain(F::Bits::load(&self.bits, order))
+    }
+
+    /// Store a flags value, replacing the current one.
+    #[inline]
+    pub fn store(&self, flags: F, order: Ordering) {
+        F::Bits::store(&self.bits, flags.bits(), order)
+    }
+
+    /// Store a flags value, returning the previous one.
+    #[inline]
+    pub fn swap(&self, flags: F, order: Ordering) -> F {
+        F::from_bits_retain(F::Bits::swap(&self.bits, flags.bits(), order))
+    }
+
+    /// The bitwise or (`|`) of the bits in the current and given flags values,
+    /// returning the previous flags value.
+    ///
+    /// This is the atomic equivalent of [`Flags::insert`].
+    #[inline]
+    pub fn fetch_insert(&self, flags: F, order: Ordering) -> F {
+        F::from_bits_retain(F::Bits::fetch_or(&self.bits, flags.bits(), order))
+    }
+
+    /// The intersection of the current flags value with the complement of the given one (`&!`),
+    /// returning the previous flags value.
+    ///
+    /// This is the atomic equivalent of [`Flags::remove`]. The given flags value isn't truncated.
+    #[inline]
+    pub fn fetch_remove(&self, flags: F, order: Ordering) -> F {
+        F::from_bits_retain(F::Bits::fetch_and(&self.bits, !flags.bits(), order))
+    }
+
+    /// The bitwise exclusive-or (`^`) of the bits in the current and given flags values,
+    /// returning the previous flags value.
+    ///
+    /// This is the atomic equivalent of [`Flags::toggle`].
+    #[inline]
+    pub fn fetch_toggle(&self, flags: F, order: Ordering) -> F {
+        F::from_bits_retain(F::Bits::fetch_xor(&self.bits, flags.bits(), order))
+    }
+
+    /// The bitwise and (`&`) of the bits in the current and given flags values,
+    /// returning the previous flags value.
+    #[inline]
+    pub fn fetch_intersect(&self, flags: F, order: Ordering) -> F {
+        F::from_bits_retain(F::Bits::fetch_and(&self.bits, flags.bits(), order))
+    }
+
+    /// Store `new` if the current flags value is exactly `current`.
+    ///
+    /// The returned value is the previous flags value. It's `Ok` if the exchange happened.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: F,
+        new: F,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<F, F> {
+        F::Bits::compare_exchange(&self.bits, current.bits(), new.bits(), success, failure)
+            .map(F::from_bits_retain)
+            .map_err(F::from_bits_retain)
+    }
+
+    /// Store `new` if the current flags value is exactly `current`.
+    ///
+    /// Unlike [`Atomic::compare_exchange`], this method may fail spuriously.
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: F,
+        new: F,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<F, F> {
+        F::Bits::compare_exchange_weak(&self.bits, current.bits(), new.bits(), success, failure)
+            .map(F::from_bits_retain)
+            .map_err(F::from_bits_retain)
+    }
+
+    /// Update the flags value with a function, retrying until no other thread
+    /// has changed it in the meantime.
+    ///
+    /// The function may be called multiple times. If it returns `None` then the update is
+    /// abandoned and `Err` is returned with the current flags value. Otherwise `Ok` is returned
+    /// with the previous flags value.
+    pub fn fetch_update(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: impl FnMut(F) -> Option<F>,
+    ) -> Result<F, F> {
+        let mut prev = F::Bits::load(&self.bits, fetch_order);
+
+        while let Some(next) = f(F::from_bits_retain(prev)) {
+            match F::Bits::compare_exchange_weak(
+                &self.bits,
+                prev,
+                next.bits(),
+                set_order,
+                fetch_order,
+            ) {
+                Ok(prev) => return Ok(F::from_bits_retain(prev)),
+                Err(next_prev) => prev = next_prev,
+            }
+        }
+
+        Err(F::from_bits_retain(prev))
+    }
+}
+
+impl<F: Flags> Default for Atomic<F>
+where
+    F::Bits: AtomicBits,
+{
+    fn default() -> Self {
+        Atomic::new(F::empty())
+    }
+}
+
+impl<F: Flags> From<F> for Atomic<F>
+where
+    F::Bits: AtomicBits,
+{
+    fn from(flags: F) -> Self {
+        Atomic::new(flags)
+    }
+}
+
+impl<F: Flags + fmt::Debug> fmt::Debug for Atomic<F>
+where
+    F::Bits: AtomicBits,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
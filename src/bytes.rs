@@ -0,0 +1,286 @@
+/*!
+Encoding flags values as bytes.
+
+Flags values can be encoded as the bytes of their underlying bits value in either
+little-endian or big-endian order:
+
+```
+use bitflags::{bitflags, Flags};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Header: u16 {
+        const SYN = 1;
+        const ACK = 1 << 1;
+        const FIN = 1 << 8;
+    }
+}
+
+let header = Header::SYN | Header::FIN;
+
+assert_eq!([0b0000_0001, 0b0000_0001], header.to_be_bytes());
+assert_eq!(Some(header), Header::from_be_bytes([0b0000_0001, 0b0000_0001]));
+```
+
+These methods are available on the [`Flags`] trait, and as functions in this module.
+Decoding comes in the same variants as converting from bits:
+
+- [`from_le_bytes`] and [`from_be_bytes`] return `None` if any unknown bits are set,
+  like [`Flags::from_bits`].
+- [`from_le_bytes_truncate`] and [`from_be_bytes_truncate`] unset any unknown bits,
+  like [`Flags::from_bits_truncate`].
+- [`from_le_bytes_retain`] and [`from_be_bytes_retain`] keep any unknown bits,
+  like [`Flags::from_bits_retain`].
+
+Bits types implement [`ToBytes`] and [`FromBytes`] to be encoded. All primitive integers
+implement them, and so does [`Words<N>`](crate::Words) for `N` up to `16`.
+
+## Bit order
+
+The functions at the root of this module number bits from the least significant, so
+bit `0` is `1`. Some protocols number bits from the most significant instead, so bit `0` of
+a `u8` is `0b1000_0000`. The [`msb0`] module encodes and decodes with this bit order, so
+flags can be defined by the bit numbers used in the protocol's specification.
+*/
+
+use crate::Flags;
+
+/**
+Encode a value as bytes.
+*/
+pub trait ToBytes {
+    /// The bytes of the value.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]>;
+
+    /// Encode the value as little-endian bytes.
+    fn to_le_bytes(&self) -> Self::Bytes;
+
+    /// Encode the value as big-endian bytes.
+    fn to_be_bytes(&self) -> Self::Bytes;
+}
+
+/**
+Decode a value from bytes.
+*/
+pub trait FromBytes: ToBytes {
+    /// Decode the value from little-endian bytes.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Decode the value from big-endian bytes.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+}
+
+/// The bytes of a flags value.
+pub type BytesOf<B> = <<B as Flags>::Bits as ToBytes>::Bytes;
+
+/**
+Encode a flags value as little-endian bytes.
+*/
+pub fn to_le_bytes<B: Flags>(flags: &B) -> BytesOf<B>
+where
+    B::Bits: ToBytes,
+{
+    flags.bits().to_le_bytes()
+}
+
+/**
+Encode a flags value as big-endian bytes.
+*/
+pub fn to_be_bytes<B: Flags>(flags: &B) -> BytesOf<B>
+where
+    B::Bits: ToBytes,
+{
+    flags.bits().to_be_bytes()
+}
+
+/**
+Decode a flags value from little-endian bytes.
+
+This function will return `None` if any unknown bits are set.
+*/
+pub fn from_le_bytes<B: Flags>(bytes: BytesOf<B>) -> Option<B>
+where
+    B::Bits: FromBytes,
+{
+    B::from_bits(B::Bits::from_le_bytes(bytes))
+}
+
+/**
+Decode a flags value from big-endian bytes.
+
+This function will return `None` if any unknown bits are set.
+*/
+pub fn from_be_bytes<B: Flags>(bytes: BytesOf<B>) -> Option<B>
+where
+    B::Bits: FromBytes,
+{
+    B::from_bits(B::Bits::from_be_bytes(bytes))
+}
+
+/**
+Decode a flags value from little-endian bytes, unsetting any unknown bits.
+*/
+pub fn from_le_bytes_truncate<B: Flags>(bytes: BytesOf<B>) -> B
+where
+    B::Bits: FromBytes,
+{
+    B::from_bits_truncate(B::Bits::from_le_bytes(bytes))
+}
+
+/**
+Decode a flags value from big-endian bytes, unsetting any unknown bits.
+*/
+pub fn from_be_bytes_truncate<B: Flags>(bytes: BytesOf<B>) -> B
+where
+    B::Bits: FromBytes,
+{
+    B::from_bits_truncate(B::Bits::from_be_bytes(bytes))
+}
+
+/**
+Decode a flags value from little-endian bytes, retaining any unknown bits.
+*/
+pub fn from_le_bytes_retain<B: Flags>(bytes: BytesOf<B>) -> B
+where
+    B::Bits: FromBytes,
+{
+    B::from_bits_retain(B::Bits::from_le_bytes(bytes))
+}
+
+/**
+Decode a flags value from big-endian bytes, retaining any unknown bits.
+*/
+pub fn from_be_bytes_retain<B: Flags>(bytes: BytesOf<B>) -> B
+where
+    B::Bits: FromBytes,
+{
+    B::from_bits_retain(B::Bits::from_be_bytes(bytes))
+}
+
+pub mod msb0 {
+    /*!
+    Encoding flags values as bytes, numbering bits from the most significant.
+
+    The functions in this module mirror the ones in the [parent module](super), but reverse
+    the order of bits in the encoded value, so bit `0` of a flags value is the most significant
+    bit of the encoded value:
+
+    ```
+    use bitflags::{bitflags, bytes::msb0};
+
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Header: u16 {
+            // The protocol numbers this bit 0
+            const VERSION = 1 << 0;
+            // The protocol numbers this bit 15
+            const LAST = 1 << 15;
+        }
+    }
+
+    assert_eq!([0b1000_0000, 0b0000_0000], msb0::to_be_bytes(&Header::VERSION));
+    assert_eq!([0b0000_0000, 0b0000_0001], msb0::to_be_bytes(&Header::LAST));
+    ```
+    */
+
+    use super::{BytesOf, FromBytes, ToBytes};
+    use crate::Flags;
+
+    /**
+    Encode a flags value as little-endian bytes, with bit `0` as the most significant bit.
+    */
+    pub fn to_le_bytes<B: Flags>(flags: &B) -> BytesOf<B>
+    where
+        B::Bits: ToBytes,
+    {
+        // Reversing all bits in the value is the same as reversing the bits in each byte,
+        // and reversing the order of the bytes
+        reverse_each(flags.bits().to_be_bytes())
+    }
+
+    /**
+    Encode a flags value as big-endian bytes, with bit `0` as the most significant bit.
+    */
+    pub fn to_be_bytes<B: Flags>(flags: &B) -> BytesOf<B>
+    where
+        B::Bits: ToBytes,
+    {
+        reverse_each(flags.bits().to_le_bytes())
+    }
+
+    /**
+    Decode a flags value from little-endian bytes, with bit `0` as the most significant bit.
+
+    This function will return `None` if any unknown bits are set.
+    */
+    pub fn from_le_bytes<B: Flags>(bytes: BytesOf<B>) -> Option<B>
+    where
+        B::Bits: FromBytes,
+    {
+        B::from_bits(B::Bits::from_be_bytes(reverse_each(bytes)))
+    }
+
+    /**
+    Decode a flags value from big-endian bytes, with bit `0` as the most significant bit.
+
+    This function will return `None` if any unknown bits are set.
+    */
+    pub fn from_be_bytes<B: Flags>(bytes: BytesOf<B>) -> Option<B>
+    where
+        B::Bits: FromBytes,
+    {
+        B::from_bits(B::Bits::from_le_bytes(reverse_each(bytes)))
+    }
+
+    /**
+    Decode a flags value from little-endian bytes, with bit `0` as the most significant bit,
+    unsetting any unknown bits.
+    */
+    pub fn from_le_bytes_truncate<B: Flags>(bytes: BytesOf<B>) -> B
+    where
+        B::Bits: FromBytes,
+    {
+        B::from_bits_truncate(B::Bits::from_be_bytes(reverse_each(bytes)))
+    }
+
+    /**
+    Decode a flags value from big-endian bytes, with bit `0` as the most significant bit,
+    unsetting any unknown bits.
+    */
+    pub fn from_be_bytes_truncate<B: Flags>(bytes: BytesOf<B>) -> B
+    where
+        B::Bits: FromBytes,
+    {
+        B::from_bits_truncate(B::Bits::from_le_bytes(reverse_each(bytes)))
+    }
+
+    /**
+    Decode a flags value from little-endian bytes, with bit `0` as the most significant bit,
+    retaining any unknown bits.
+    */
+    pub fn from_le_bytes_retain<B: Flags>(bytes: BytesOf<B>) -> B
+    where
+        B::Bits: FromBytes,
+    {
+        B::from_bits_retain(B::Bits::from_be_bytes(reverse_each(bytes)))
+    }
+
+    /**
+    Decode a flags value from big-endian bytes, with bit `0` as the most significant bit,
+    retaining any unknown bits.
+    */
+    pub fn from_be_bytes_retain<B: Flags>(bytes: BytesOf<B>) -> B
+    where
+        B::Bits: FromBytes,
+    {
+        B::from_bits_retain(B::Bits::from_le_bytes(reverse_each(bytes)))
+    }
+
+    fn reverse_each<T: AsMut<[u8]>>(mut bytes: T) -> T {
+        for byte in bytes.as_mut() {
+            *byte = byte.reverse_bits();
+        }
+
+        bytes
+    }
+}
@@ -0,0 +1,366 @@
+//! Parsing flags from text in const contexts.
+//!
+//! This module backs the [`flags`](crate::flags) macro. The parser can't call any methods on
+//! the flags type, so it only finds the index of each named flag, or the value of each number,
+//! and the macro combines them.
+
+use crate::{parser::ParseErrorKind, Flag};
+
+/// A parser over the flags in some text, generated by the `flags!` macro.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct ConstParser {
+    input: &'static str,
+    pos: usize,
+    size: usize,
+    done: bool,
+    // The byte range of the last flag parsed
+    start: usize,
+    end: usize,
+}
+
+/// The next flag parsed by a `ConstParser`.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub enum ConstFlag {
+    End,
+    Named(usize),
+    Bits(u128),
+    Error(ParseErrorKind),
+}
+
+impl ConstParser {
+    /// Parse flags from `input`, where the bits type is `size` bytes.
+    pub const fn new(input: &'static str, size: usize) -> Self {
+        ConstParser {
+            input,
+            pos: 0,
+            size,
+            // If the input is empty then there are no flags
+            done: is_blank(input.as_bytes(), 0, input.len()),
+            start: 0,
+            end: 0,
+        }
+    }
+
+    /// Parse the next flag.
+    ///
+    /// This method returns the parser to use for the flag after this one.
+    pub const fn next<B>(self, flags: &'static [Flag<B>]) -> (Self, ConstFlag) {
+        if self.done {
+            return (self, ConstFlag::End);
+        }
+
+        let input = self.input.as_bytes();
+
+        let start = self.pos;
+        let mut separator = start;
+        while separator < input.len() && input[separator] != b'|' {
+            separator += 1;
+        }
+
+        let (start, end) = trim(input, start, separator);
+
+        let next = ConstParser {
+            input: self.input,
+            pos: separator + 1,
+            size: self.size,
+            done: separator >= input.len(),
+            start,
+            end,
+        };
+
+        // If the flag is empty then we've got missing input
+        if start == end {
+            return (next, ConstFlag::Error(ParseErrorKind::EmptyFlag));
+        }
+
+        // If the flag starts with a digit then it's a number
+        if input[start].is_ascii_digit() {
+            return (next, parse_number(input, start, end, self.size));
+        }
+
+        // Fields can't be parsed, because their variants can't be looked up in const contexts
+        let mut i = start;
+        while i < end {
+            if input[i] == b'=' {
+                return (next, ConstFlag::Error(ParseErrorKind::InvalidNamedFlag));
+            }
+
+            i += 1;
+        }
+
+        // Otherwise the flag is a name
+        let mut i = 0;
+        while i < flags.len() {
+            if has_name(&flags[i], input, start, end) {
+                return (next, ConstFlag::Named(i));
+            }
+
+            i += 1;
+        }
+
+        (next, ConstFlag::Error(ParseErrorKind::InvalidNamedFlag))
+    }
+}
+
+/// The error message for a flag that couldn't be parsed by a `ConstParser`, in a buffer of
+/// `N` bytes.
+#[doc(hidden)]
+pub struct ConstError<const N: usize> {
+    kind: ParseErrorKind,
+    buf: [u8; N],
+    len: usize,
+}
+
+impl ConstParser {
+    /// The error message for the last flag parsed, which failed with the given `kind`.
+    ///
+    /// Any parts of the message that don't fit in `N` bytes are left out. Twice the length of
+    /// the input plus 96 bytes is always enough.
+    pub const fn error<const N: usize>(&self, kind: ParseErrorKind) -> ConstError<N> {
+        let input = self.input.as_bytes();
+
+        let error = ConstError {
+            kind,
+            buf: [0; N],
+            len: 0,
+        }
+        .push_str("failed to parse `")
+        .push_str(self.input)
+        .push_str("`: ");
+
+        let error = match kind {
+            ParseErrorKind::InvalidNamedFlag => error.push_str("unrecognized named flag"),
+            ParseErrorKind::InvalidHexFlag => error.push_str("invalid hex flag"),
+            ParseErrorKind::InvalidNumberFlag => error.push_str("invalid number flag"),
+            ParseErrorKind::EmptyFlag => error.push_str("encountered empty flag"),
+        };
+
+        // Like `ParseError`, name the flag that caused the error, if any, and where it is
+        let error = if self.start == self.end {
+            error
+        } else {
+            error
+                .push_str(" `")
+                .push(input, self.start, self.end)
+                .push_str("`")
+        };
+
+        error
+            .push_str(" at ")
+            .push_usize(self.start)
+            .push_str("..")
+            .push_usize(self.end)
+    }
+}
+
+impl<const N: usize> ConstError<N> {
+    /// The error message.
+    ///
+    /// Compilers before 1.63 can't check UTF-8 in const contexts, so the message only
+    /// describes the kind of error on those.
+    #[cfg(bitflags_const_from_utf8)]
+    #[clippy::msrv = "1.63"]
+    pub const fn as_str(&self) -> &str {
+        // Strip the unused end of the buffer
+        let mut message: &[u8] = &self.buf;
+        while message.len() > self.len {
+            message = match message {
+                [rest @ .., _] => rest,
+                [] => break,
+            };
+        }
+
+        // The message is only split between `char`s, so it's always valid UTF-8
+        match core::str::from_utf8(message) {
+            Ok(message) => message,
+            Err(_) => self.kind_str(),
+        }
+    }
+
+    /// The error message.
+    ///
+    /// Compilers before 1.63 can't check UTF-8 in const contexts, so the message only
+    /// describes the kind of error on those.
+    #[cfg(not(bitflags_const_from_utf8))]
+    pub const fn as_str(&self) -> &str {
+        self.kind_str()
+    }
+
+    const fn kind_str(&self) -> &'static str {
+        match self.kind {
+            ParseErrorKind::InvalidNamedFlag => "failed to parse flags: unrecognized named flag",
+            ParseErrorKind::InvalidHexFlag => "failed to parse flags: invalid hex flag",
+            ParseErrorKind::InvalidNumberFlag => "failed to parse flags: invalid number flag",
+            ParseErrorKind::EmptyFlag => "failed to parse flags: encountered empty flag",
+        }
+    }
+
+    // Append some text to the message, unless it doesn't fit
+    const fn push_str(self, s: &str) -> Self {
+        self.push(s.as_bytes(), 0, s.len())
+    }
+
+    // Append `bytes[start..end]` to the message, unless it doesn't fit
+    const fn push(mut self, bytes: &[u8], start: usize, end: usize) -> Self {
+        if self.len + (end - start) > N {
+            return self;
+        }
+
+        let mut i = start;
+        while i < end {
+            self.buf[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+
+        self
+    }
+
+    // Append a number to the message in decimal
+    const fn push_usize(self, mut value: usize) -> Self {
+        let mut digits = [0; 20];
+        let mut start = digits.len();
+
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+
+            if value == 0 {
+                break;
+            }
+        }
+
+        self.push(&digits, start, digits.len())
+    }
+}
+
+// Parse a number with an optional `0x`, `0b`, or `0o` prefix
+const fn parse_number(input: &[u8], start: usize, end: usize, size: usize) -> ConstFlag {
+    let (base, start, invalid) = if end - start >= 2 && input[start] == b'0' {
+        match input[start + 1] {
+            b'x' => (16, start + 2, ParseErrorKind::InvalidHexFlag),
+            b'b' => (2, start + 2, ParseErrorKind::InvalidNumberFlag),
+            b'o' => (8, start + 2, ParseErrorKind::InvalidNumberFlag),
+            _ => (10, start, ParseErrorKind::InvalidNumberFlag),
+        }
+    } else {
+        (10, start, ParseErrorKind::InvalidNumberFlag)
+    };
+
+    let mut value: u128 = 0;
+    let mut any_digits = false;
+
+    let mut i = start;
+    while i < end {
+        let b = input[i];
+        i += 1;
+
+        // Digits may be separated by any number of `_`
+        if b == b'_' {
+            continue;
+        }
+
+        let digit = match b {
+            b'0'..=b'9' => b - b'0',
+            b'a'..=b'f' => b - b'a' + 10,
+            b'A'..=b'F' => b - b'A' + 10,
+            _ => return ConstFlag::Error(invalid),
+        };
+
+        if digit as u128 >= base {
+            return ConstFlag::Error(invalid);
+        }
+
+        value = match value.checked_mul(base) {
+            Some(value) => match value.checked_add(digit as u128) {
+                Some(value) => value,
+                None => return ConstFlag::Error(invalid),
+            },
+            None => return ConstFlag::Error(invalid),
+        };
+        any_digits = true;
+    }
+
+    // The number must fit in the bits type
+    if !any_digits || (size < 16 && value >> (size * 8) != 0) {
+        return ConstFlag::Error(invalid);
+    }
+
+    ConstFlag::Bits(value)
+}
+
+// Whether a flag has the name in `input[start..end]`, as its name, display name, or an alias
+const fn has_name<B>(flag: &Flag<B>, input: &[u8], start: usize, end: usize) -> bool {
+    if !flag.is_named() {
+        return false;
+    }
+
+    if eq(flag.name(), input, start, end) || eq(flag.display_name(), input, start, end) {
+        return true;
+    }
+
+    let aliases = flag.aliases();
+    let mut i = 0;
+    while i < aliases.len() {
+        if eq(aliases[i], input, start, end) {
+            return true;
+        }
+
+        i += 1;
+    }
+
+    let aliases = flag.deprecated_aliases();
+    let mut i = 0;
+    while i < aliases.len() {
+        if eq(aliases[i], input, start, end) {
+            return true;
+        }
+
+        i += 1;
+    }
+
+    false
+}
+
+// Whether `name` is equal to `input[start..end]`
+const fn eq(name: &str, input: &[u8], start: usize, end: usize) -> bool {
+    let name = name.as_bytes();
+
+    if name.len() != end - start {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < name.len() {
+        if name[i] != input[start + i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+// Trim any ASCII whitespace from the start and end of `input[start..end]`
+const fn trim(input: &[u8], mut start: usize, mut end: usize) -> (usize, usize) {
+    while start < end && input[start].is_ascii_whitespace() {
+        start += 1;
+    }
+
+    while end > start && input[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+
+    (start, end)
+}
+
+// Whether `input[start..end]` is empty or only ASCII whitespace
+const fn is_blank(input: &[u8], start: usize, end: usize) -> bool {
+    let (start, end) = trim(input, start, end);
+
+    start == end
+}
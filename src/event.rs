@@ -0,0 +1,531 @@
+/*!
+Wait for flags to be set by other threads or tasks.
+
+An [`EventGroup`] holds a flags value that producers [`set`](EventGroup::set) and
+[`clear`](EventGroup::clear), and that consumers wait on until some flags are set. Waiting
+can either be done asynchronously through a future that's woken when flags are set, or by
+blocking the current thread with the `std` feature enabled:
+
+```
+use std::{sync::Arc, thread};
+
+use bitflags::{
+    bitflags,
+    event::{EventGroup, Wait},
+};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Events: u8 {
+        const CONNECTED = 1;
+        const READY = 1 << 1;
+    }
+}
+
+let events = Arc::new(EventGroup::new(Events::empty()));
+
+let producer = {
+    let events = events.clone();
+
+    thread::spawn(move || {
+        events.set(Events::CONNECTED);
+        events.set(Events::READY);
+    })
+};
+
+// Block until both flags are set, clearing them before returning
+let set = events.wait(Wait::all(Events::CONNECTED | Events::READY).and_clear());
+
+assert_eq!(Events::CONNECTED | Events::READY, set);
+assert_eq!(Events::empty(), events.get());
+
+producer.join().unwrap();
+```
+
+Futures returned by [`EventGroup::wait_async`] don't allocate or depend on any particular
+async runtime, so they also work without the `std` feature. A waiting future stores its waker
+in one of a fixed number of slots in the group, given by its `WAITERS` parameter. If all the
+slots are taken then the future wakes itself to be polled again, so it still completes, but
+keeps its task busy while it waits.
+
+Without the `std` feature, setting flags never blocks or spins, so it can also be done from
+interrupt handlers.
+
+This module requires the `atomic` feature, and is only available on targets that support
+atomic compare-and-swap operations.
+*/
+
+#![cfg(target_has_atomic = "8")]
+#![allow(unsafe_code)]
+
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU8, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+#[cfg(feature = "std")]
+use std::{
+    sync::{Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    atomic::{Atomic, AtomicBits},
+    Flags,
+};
+
+/**
+A condition to wait for on an [`EventGroup`].
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Wait<F> {
+    mask: F,
+    all: bool,
+    clear: bool,
+}
+
+impl<F: Flags> Wait<F> {
+    /// Wait until all bits in `mask` are set.
+    ///
+    /// This is the same condition as [`Flags::contains`].
+    pub fn all(mask: F) -> Self {
+        Wait {
+            mask,
+            all: true,
+            clear: false,
+        }
+    }
+
+    /// Wait until any bits in `mask` are set.
+    ///
+    /// This is the same condition as [`Flags::intersects`], except an empty `mask` is
+    /// always satisfied, like it is for [`Wait::all`].
+    pub fn any(mask: F) -> Self {
+        Wait {
+            mask,
+            all: false,
+            clear: false,
+        }
+    }
+
+    /// Clear the bits in the mask when the condition is satisfied.
+    ///
+    /// Checking the condition and clearing the bits happens atomically, so only
+    /// one waiter will observe the bits being set.
+    pub fn and_clear(mut self) -> Self {
+        self.clear = true;
+        self
+    }
+
+    fn mask(&self) -> F {
+        F::from_bits_retain(self.mask.bits())
+    }
+
+    fn is_satisfied(&self, flags: &F) -> bool {
+        let mask = self.mask();
+
+        // No bits intersect an empty mask, but there's nothing to wait for
+        if self.all || mask.is_empty() {
+            flags.contains(mask)
+        } else {
+            flags.intersects(mask)
+        }
+    }
+}
+
+/**
+A flags value that can be waited on.
+
+Waiters are woken whenever flags are set. See the [module docs](index.html) for an example.
+*/
+pub struct EventGroup<F: Flags, const WAITERS: usize = 8>
+where
+    F::Bits: AtomicBits,
+{
+    flags: Atomic<F>,
+    slots: [WakerSlot; WAITERS],
+    #[cfg(feature = "std")]
+    lock: Mutex<()>,
+    #[cfg(feature = "std")]
+    condvar: Condvar,
+}
+
+impl<F: Flags> EventGroup<F>
+where
+    F::Bits: AtomicBits,
+{
+    /// Create a new event group with some initial flags set.
+    ///
+    /// The group has room for 8 waiting futures. Use [`EventGroup::with_waiters`]
+    /// for a different number.
+    pub fn new(flags: F) -> Self {
+        EventGroup::with_waiters(flags)
+    }
+}
+
+impl<F: Flags, const WAITERS: usize> EventGroup<F, WAITERS>
+where
+    F::Bits: AtomicBits,
+{
+    /// Create a new event group with some initial flags set, and room for `WAITERS`
+    /// waiting futures.
+    pub fn with_waiters(flags: F) -> Self {
+        EventGroup {
+            flags: Atomic::new(flags),
+            slots: [WakerSlot::FREE; WAITERS],
+            #[cfg(feature = "std")]
+            lock: Mutex::new(()),
+            #[cfg(feature = "std")]
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Get the current flags value.
+    pub fn get(&self) -> F {
+        self.flags.load(Ordering::SeqCst)
+    }
+
+    /// Set the bits in `flags`, waking any waiters.
+    ///
+    /// The returned value is the flags value before the bits were set.
+    pub fn set(&self, flags: F) -> F {
+        let prev = self.flags.fetch_insert(flags, Ordering::SeqCst);
+
+        for slot in self.slots.iter() {
+            if let Some(waker) = slot.take() {
+                waker.wake();
+            }
+        }
+
+        #[cfg(feature = "std")]
+        {
+            // Acquiring the lock means a blocked thread can't miss this notification
+            // between checking the flags and waiting
+            drop(self.lock());
+            self.condvar.notify_all();
+        }
+
+        prev
+    }
+
+    /// Unset the bits in `flags`.
+    ///
+    /// The returned value is the flags value before the bits were unset.
+    pub fn clear(&self, flags: F) -> F {
+        self.flags.fetch_remove(flags, Ordering::SeqCst)
+    }
+
+    /// Block the current thread until a condition is satisfied.
+    ///
+    /// The returned value is the flags value that satisfied the condition,
+    /// before any bits were cleared.
+    #[cfg(feature = "std")]
+    pub fn wait(&self, wait: Wait<F>) -> F {
+        let mut guard = self.lock();
+
+        loop {
+            if let Some(flags) = self.try_take(&wait) {
+                return flags;
+            }
+
+            guard = self
+                .condvar
+                .wait(guard)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    /// Block the current thread until a condition is satisfied, or the timeout elapses.
+    ///
+    /// This method will return `None` if the condition wasn't satisfied before the timeout.
+    #[cfg(feature = "std")]
+    pub fn wait_timeout(&self, wait: Wait<F>, timeout: Duration) -> Option<F> {
+        let deadline = Instant::now() + timeout;
+        let mut guard = self.lock();
+
+        loop {
+            if let Some(flags) = self.try_take(&wait) {
+                return Some(flags);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+
+            guard = self
+                .condvar
+                .wait_timeout(guard, deadline - now)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+    }
+
+    /// Check a condition without waiting.
+    ///
+    /// This method will return `None` if the condition isn't currently satisfied.
+    pub fn try_wait(&self, wait: Wait<F>) -> Option<F> {
+        self.try_take(&wait)
+    }
+
+    fn try_take(&self, wait: &Wait<F>) -> Option<F> {
+        if !wait.clear {
+            let flags = self.get();
+
+            return if wait.is_satisfied(&flags) {
+                Some(flags)
+            } else {
+                None
+            };
+        }
+
+        self.flags
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |flags| {
+                if wait.is_satisfied(&flags) {
+                    Some(flags.difference(wait.mask()))
+                } else {
+                    None
+                }
+            })
+            .ok()
+    }
+
+    /// Get a future that completes when a condition is satisfied.
+    ///
+    /// The future doesn't depend on any particular async runtime.
+    pub fn wait_async(&self, wait: Wait<F>) -> WaitFuture<'_, F, WAITERS> {
+        WaitFuture {
+            group: self,
+            wait,
+            slot: None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        // The lock doesn't guard any state, so it's fine if a thread panicked while holding it
+        self.lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // Claim a free slot for a waiting future's waker
+    fn claim(&self, waker: &Waker) -> Option<usize> {
+        self.slots.iter().position(|slot| slot.claim(waker))
+    }
+}
+
+impl<F: Flags, const WAITERS: usize> Default for EventGroup<F, WAITERS>
+where
+    F::Bits: AtomicBits,
+{
+    fn default() -> Self {
+        EventGroup::with_waiters(F::empty())
+    }
+}
+
+impl<F: Flags + fmt::Debug, const WAITERS: usize> fmt::Debug for EventGroup<F, WAITERS>
+where
+    F::Bits: AtomicBits,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EventGroup").field(&self.get()).finish()
+    }
+}
+
+/**
+A future that completes when a condition on an [`EventGroup`] is satisfied.
+
+This type is returned by [`EventGroup::wait_async`]. Dropping the future before it completes
+frees its waker slot in the group.
+*/
+#[must_use = "futures do nothing unless polled"]
+pub struct WaitFuture<'a, F: Flags, const WAITERS: usize = 8>
+where
+    F::Bits: AtomicBits,
+{
+    group: &'a EventGroup<F, WAITERS>,
+    wait: Wait<F>,
+    slot: Option<usize>,
+}
+
+impl<'a, F: Flags, const WAITERS: usize> WaitFuture<'a, F, WAITERS>
+where
+    F::Bits: AtomicBits,
+{
+    fn release(&mut self) {
+        if let Some(slot) = self.slot.take() {
+            self.group.slots[slot].release();
+        }
+    }
+}
+
+// The future is never pinned structurally
+impl<'a, F: Flags, const WAITERS: usize> Unpin for WaitFuture<'a, F, WAITERS> where
+    F::Bits: AtomicBits
+{
+}
+
+impl<'a, F: Flags, const WAITERS: usize> Future for WaitFuture<'a, F, WAITERS>
+where
+    F::Bits: AtomicBits,
+{
+    type Output = F;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F> {
+        let this = self.get_mut();
+
+        if let Some(flags) = this.group.try_take(&this.wait) {
+            this.release();
+            return Poll::Ready(flags);
+        }
+
+        // Register to be woken the next time flags are set
+        match this.slot {
+            Some(slot) => this.group.slots[slot].update(cx.waker()),
+            None => this.slot = this.group.claim(cx.waker()),
+        }
+
+        // Check again, in case flags were set before the waker was registered
+        if let Some(flags) = this.group.try_take(&this.wait) {
+            this.release();
+            return Poll::Ready(flags);
+        }
+
+        // If every slot is taken then poll again as soon as possible
+        if this.slot.is_none() {
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<'a, F: Flags, const WAITERS: usize> Drop for WaitFuture<'a, F, WAITERS>
+where
+    F::Bits: AtomicBits,
+{
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/*
+The waker of a waiting future.
+
+A slot is claimed by a future while it's waiting, and released when it completes or is dropped.
+The waker is only accessed while the slot is `BUSY`, which acts as a lock. The future that
+claimed the slot waits for that lock, but setting flags skips any busy slots, so it never
+waits for a future, even one interrupted while holding the lock. That's fine, because the
+future always checks the flags again after releasing the lock.
+*/
+struct WakerSlot {
+    state: AtomicU8,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+const FREE: u8 = 0;
+const CLAIMED: u8 = 1;
+const BUSY: u8 = 2;
+
+// SAFETY: The waker is only accessed by whoever moved the slot into the `BUSY` state
+unsafe impl Sync for WakerSlot {}
+
+impl WakerSlot {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const FREE: WakerSlot = WakerSlot {
+        state: AtomicU8::new(FREE),
+        waker: UnsafeCell::new(None),
+    };
+
+    // Claim the slot for a waker if it's free
+    fn claim(&self, waker: &Waker) -> bool {
+        match self.try_lock(FREE) {
+            Some(slot) => {
+                slot.unlock(Some(waker.clone()), CLAIMED);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Replace the waker in a claimed slot
+    fn update(&self, waker: &Waker) {
+        let slot = self.lock();
+
+        let waker = match slot.waker() {
+            Some(current) if current.will_wake(waker) => None,
+            _ => Some(waker.clone()),
+        };
+
+        match waker {
+            Some(waker) => drop(slot.unlock(Some(waker), CLAIMED)),
+            None => slot.release(CLAIMED),
+        }
+    }
+
+    // Free a claimed slot
+    fn release(&self) {
+        drop(self.lock().unlock(None, FREE));
+    }
+
+    // Take the waker from a claimed slot, without waiting for the lock
+    fn take(&self) -> Option<Waker> {
+        self.try_lock(CLAIMED)?.unlock(None, CLAIMED)
+    }
+
+    // Lock a claimed slot, waiting until it isn't busy
+    fn lock(&self) -> SlotGuard<'_> {
+        loop {
+            if let Some(slot) = self.try_lock(CLAIMED) {
+                return slot;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+
+    fn try_lock(&self, from: u8) -> Option<SlotGuard<'_>> {
+        self.state
+            .compare_exchange(from, BUSY, Ordering::SeqCst, Ordering::SeqCst)
+            .ok()
+            .map(|_| SlotGuard { slot: self, from })
+    }
+}
+
+// A locked slot, which restores its previous state if it's dropped without being unlocked
+struct SlotGuard<'a> {
+    slot: &'a WakerSlot,
+    from: u8,
+}
+
+impl<'a> SlotGuard<'a> {
+    fn waker(&self) -> Option<&Waker> {
+        // SAFETY: The slot is locked, so nothing else can access the waker
+        unsafe { &*self.slot.waker.get() }.as_ref()
+    }
+
+    // Replace the waker and unlock the slot, returning the previous waker
+    fn unlock(self, waker: Option<Waker>, to: u8) -> Option<Waker> {
+        // SAFETY: The slot is locked, so nothing else can access the waker
+        let prev = core::mem::replace(unsafe { &mut *self.slot.waker.get() }, waker);
+
+        self.release(to);
+        prev
+    }
+
+    fn release(self, to: u8) {
+        self.slot.state.store(to, Ordering::SeqCst);
+        core::mem::forget(self);
+    }
+}
+
+impl<'a> Drop for SlotGuard<'a> {
+    fn drop(&mut self) {
+        self.slot.state.store(self.from, Ordering::SeqCst);
+    }
+}
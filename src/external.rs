@@ -74,6 +74,12 @@ pub(crate) mod __private {
 
     #[cfg(feature = "bytemuck")]
     pub use bytemuck;
+
+    #[cfg(feature = "schemars")]
+    pub use schemars;
+
+    #[cfg(feature = "schemars")]
+    pub use alloc::borrow::Cow;
 }
 
 /// Implements traits from external libraries for the internal bitflags type.
@@ -118,6 +124,15 @@ macro_rules! __impl_external_bitflags {
                 )*
             }
         }
+
+        $crate::__impl_external_bitflags_schemars! {
+            $InternalBitFlags: $T, $PublicBitFlags {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag;
+                )*
+            }
+        }
     };
 }
 
@@ -179,7 +194,7 @@ macro_rules! __impl_external_bitflags_serde {
 pub mod arbitrary;
 
 #[cfg(feature = "bytemuck")]
-mod bytemuck;
+pub mod bytemuck;
 
 /// Implement `Arbitrary` for the internal bitflags type.
 #[macro_export]
@@ -260,3 +275,58 @@ macro_rules! __impl_external_bitflags_bytemuck {
         }
     ) => {};
 }
+
+#[cfg(feature = "schemars")]
+pub mod schemars;
+
+/// Implement `JsonSchema` for the internal bitflags type.
+#[macro_export]
+#[doc(hidden)]
+#[cfg(feature = "schemars")]
+macro_rules! __impl_external_bitflags_schemars {
+    (
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt;
+            )*
+        }
+    ) => {
+        impl $crate::__private::schemars::JsonSchema for $InternalBitFlags {
+            fn schema_name() -> $crate::__private::Cow<'static, str> {
+                $crate::__private::Cow::Borrowed($crate::__private::core::stringify!($PublicBitFlags))
+            }
+
+            fn schema_id() -> $crate::__private::Cow<'static, str> {
+                $crate::__private::Cow::Borrowed($crate::__private::core::concat!(
+                    $crate::__private::core::module_path!(),
+                    "::",
+                    $crate::__private::core::stringify!($PublicBitFlags)
+                ))
+            }
+
+            fn json_schema(
+                generator: &mut $crate::__private::schemars::SchemaGenerator,
+            ) -> $crate::__private::schemars::Schema {
+                $crate::schemars::schema::<$PublicBitFlags>(generator)
+            }
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[cfg(not(feature = "schemars"))]
+macro_rules! __impl_external_bitflags_schemars {
+    (
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt;
+            )*
+        }
+    ) => {};
+}
+
+#[cfg(feature = "zerocopy")]
+pub mod zerocopy;
@@ -1,6 +1,6 @@
 //! Specialized fuzzing for flags types using `arbitrary`.
 
-use crate::Flags;
+use crate::{Flags, Words};
 
 /**
 Generate some arbitrary flags value with only known bits set.
@@ -12,6 +12,18 @@ where
     B::from_bits(u.arbitrary()?).ok_or(arbitrary::Error::IncorrectFormat)
 }
 
+impl<'a, const N: usize> arbitrary::Arbitrary<'a> for Words<N> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Arrays of any length only implement `Arbitrary` in newer versions of `arbitrary`
+        let mut words = [0; N];
+        for word in &mut words {
+            *word = u.arbitrary()?;
+        }
+
+        Ok(Words::from_words(words))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use arbitrary::Arbitrary;
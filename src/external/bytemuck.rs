@@ -1,9 +1,137 @@
+/*!
+Specialized casting for flags types using `bytemuck`.
+
+Generated flags types can derive `Pod` and `Zeroable`, so they can be cast to and from their
+underlying bits values:
+
+```
+use bitflags::bitflags;
+use bytemuck::{Pod, Zeroable};
+
+bitflags! {
+    #[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq, Eq)]
+    #[repr(transparent)]
+    struct Color: u32 {
+        const RED = 0x1;
+        const GREEN = 0x2;
+        const BLUE = 0x4;
+    }
+}
+
+assert_eq!(Color::RED | Color::BLUE, bytemuck::cast(0x5u32));
+```
+
+Since flags values can retain unknown bits, any bits are a valid flags value, and casts like
+`bytemuck::cast_slice` never check them. The [`Strict`] wrapper implements `CheckedBitPattern`,
+so the casts in `bytemuck::checked` only succeed if no unknown bits are set, like
+[`Flags::from_bits`]:
+
+```
+use bitflags::{bitflags, bytemuck::Strict};
+use bytemuck::{checked::CheckedCastError, Pod, Zeroable};
+
+bitflags! {
+    #[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq, Eq)]
+    #[repr(transparent)]
+    struct Color: u32 {
+        const RED = 0x1;
+        const GREEN = 0x2;
+        const BLUE = 0x4;
+    }
+}
+
+let colors: &[Strict<Color>] = bytemuck::checked::try_cast_slice(&[0x1u32, 0x6]).unwrap();
+assert_eq!(Color::GREEN | Color::BLUE, colors[1].into_inner());
+
+assert_eq!(
+    Err(CheckedCastError::InvalidBitPattern),
+    bytemuck::checked::try_cast_slice::<u32, Strict<Color>>(&[0x1, 0x8]),
+);
+```
+*/
+
+#![allow(unsafe_code)]
+
+use crate::{Flags, Words};
+use bytemuck::{
+    checked::{CheckedBitPattern, CheckedCastError},
+    NoUninit, Pod, Zeroable,
+};
+
+// SAFETY: `Words<N>` is a transparent wrapper around `[u64; N]`, which is `Pod`
+unsafe impl<const N: usize> Pod for Words<N> {}
+
+// SAFETY: `Words<N>` is a transparent wrapper around `[u64; N]`, which is `Zeroable`
+unsafe impl<const N: usize> Zeroable for Words<N> {}
+
+/**
+A flags value that doesn't contain any unknown bits.
+
+This type has the same layout as `F`, but casting to it with `bytemuck::checked` fails if any
+unknown bits are set.
+*/
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Strict<F>(F);
+
+impl<F: Flags> Strict<F> {
+    /**
+    Wrap a flags value.
+
+    This method will return `None` if any unknown bits are set, like [`Flags::from_bits`].
+    */
+    pub fn new(flags: F) -> Option<Self> {
+        F::from_bits(flags.bits()).map(Strict)
+    }
+}
+
+impl<F> Strict<F> {
+    /// Get the wrapped flags value.
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<F> AsRef<F> for Strict<F> {
+    fn as_ref(&self) -> &F {
+        &self.0
+    }
+}
+
+// SAFETY: `Strict<F>` is a transparent wrapper around `F`, so has no padding or uninit bytes
+// whenever `F` doesn't
+unsafe impl<F: NoUninit> NoUninit for Strict<F> {}
+
+// SAFETY: `Strict<F>` is a transparent wrapper around `F`, which accepts any bit pattern,
+// and this impl only narrows the valid bit patterns to those without unknown bits
+unsafe impl<F: Flags + Pod> CheckedBitPattern for Strict<F> {
+    type Bits = F;
+
+    fn is_valid_bit_pattern(bits: &F) -> bool {
+        F::from_bits(bits.bits()).is_some()
+    }
+}
+
+/**
+Cast a slice of values into a slice of flags values.
+
+This is like `bytemuck::checked::try_cast_slice` with [`Strict`], and fails with
+`CheckedCastError::InvalidBitPattern` if any unknown bits are set in any of the flags values.
+*/
+pub fn try_cast_slice<A: NoUninit, F: Flags + Pod>(a: &[A]) -> Result<&[F], CheckedCastError> {
+    let strict: &[Strict<F>] = bytemuck::checked::try_cast_slice(a)?;
+
+    // SAFETY: `Strict<F>` is a transparent wrapper around `F`, so the slices have the same layout
+    Ok(unsafe { core::slice::from_raw_parts(strict.as_ptr() as *const F, strict.len()) })
+}
+
 #[cfg(test)]
 mod tests {
-    use bytemuck::{Pod, Zeroable};
+    use super::Strict;
+    use bytemuck::{checked::CheckedCastError, Pod, Zeroable};
 
     bitflags! {
-        #[derive(Pod, Zeroable, Clone, Copy)]
+        #[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq, Eq)]
         #[repr(transparent)]
         struct Color: u32 {
             const RED = 0x1;
@@ -16,4 +144,42 @@ mod tests {
     fn test_bytemuck() {
         assert_eq!(0x1, bytemuck::cast::<Color, u32>(Color::RED));
     }
+
+    #[test]
+    fn test_strict() {
+        assert_eq!(
+            Some(Color::RED),
+            Strict::new(Color::RED).map(Strict::into_inner)
+        );
+        assert_eq!(None, Strict::new(Color::from_bits_retain(0x8)));
+
+        assert_eq!(
+            Color::GREEN | Color::BLUE,
+            bytemuck::checked::cast::<u32, Strict<Color>>(0x6).into_inner()
+        );
+        assert_eq!(
+            Err(CheckedCastError::InvalidBitPattern),
+            bytemuck::checked::try_cast::<u32, Strict<Color>>(0x9)
+        );
+
+        let colors: &[Strict<Color>] = bytemuck::checked::try_cast_slice(&[0x0u32, 0x7]).unwrap();
+        assert_eq!(&Color::all(), colors[1].as_ref());
+
+        assert_eq!(
+            Err(CheckedCastError::InvalidBitPattern),
+            bytemuck::checked::try_cast_slice::<u32, Strict<Color>>(&[0x1, 0x10])
+        );
+    }
+
+    #[test]
+    fn test_try_cast_slice() {
+        assert_eq!(
+            Ok(&[Color::RED, Color::GREEN | Color::BLUE][..]),
+            super::try_cast_slice::<u32, Color>(&[0x1, 0x6])
+        );
+        assert_eq!(
+            Err(CheckedCastError::InvalidBitPattern),
+            super::try_cast_slice::<u32, Color>(&[0x1, 0x8])
+        );
+    }
 }
@@ -0,0 +1,340 @@
+/*!
+Specialized JSON Schemas for flags types using `schemars`.
+
+Generated flags types that derive `JsonSchema` are described by [`schema`], which matches the
+text format used by [`serde`](crate::serde) for human-readable formats, like `"A | B"`. The
+format is described by a regular expression built from the names of the defined flags:
+
+```ignore
+use bitflags::bitflags;
+use schemars::JsonSchema;
+
+bitflags! {
+    #[derive(JsonSchema)]
+    #[schemars(transparent)]
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+    }
+}
+
+let schema = schemars::schema_for!(Permissions);
+
+assert_eq!(Some("string"), schema.get("type").and_then(|ty| ty.as_str()));
+```
+
+The other functions in this module describe the adapters in the [`serde`](crate::serde) module,
+and can be used with `#[schemars(schema_with = ...)]` alongside the matching
+`#[serde(with = ...)]` attribute:
+
+```ignore
+use bitflags::bitflags;
+use schemars::JsonSchema;
+
+bitflags! {
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+    }
+}
+
+#[derive(JsonSchema)]
+struct File {
+    #[schemars(schema_with = "bitflags::schemars::seq_strict_schema::<Permissions>")]
+    permissions: Permissions,
+}
+```
+
+These examples derive `JsonSchema`, which needs the `derive` feature of `schemars`, so they're
+tested in the `tests/schemars-test` crate instead.
+*/
+
+use crate::Flags;
+use alloc::{format, string::String, vec::Vec};
+use schemars::{json_schema, Schema, SchemaGenerator};
+
+// The grammar of numbers in the text format
+const NUMBER: &str = r"0x[0-9a-fA-F_]+|0b[01_]+|0o[0-7_]+|[0-9][0-9_]*";
+
+/**
+A schema for a set of flags as a string, like `"A | B"`.
+
+This describes the format used by [`serde::serialize`](crate::serde::serialize) and
+[`serde::deserialize`](crate::serde::deserialize) for human-readable formats. It's also
+used by the [`retain`](crate::serde::retain) and [`truncate`](crate::serde::truncate) adapters.
+*/
+pub fn schema<B: Flags>(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": text_pattern::<B>(true),
+    })
+}
+
+/**
+A schema for a set of flags as a string of only named flags, like `"A | B"`.
+
+This describes the format used by the [`strict`](crate::serde::strict) adapter for
+human-readable formats.
+*/
+pub fn strict_schema<B: Flags>(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": text_pattern::<B>(false),
+    })
+}
+
+/**
+A schema for a set of flags as a sequence of flags, like `["A", "B"]`.
+
+This describes the format used by the [`seq`](crate::serde::seq) adapter. Each item is
+the name of a flag, or a number for any unknown bits.
+*/
+pub fn seq_schema<B: Flags>(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": {
+            "type": "string",
+            "anyOf": [
+                { "enum": item_names::<B>() },
+                { "pattern": format!(r"^\s*(?:{})\s*$", NUMBER) },
+            ],
+        },
+    })
+}
+
+/**
+A schema for a set of flags as a sequence of only named flags, like `["A", "B"]`.
+
+This describes the format used by the [`seq::strict`](crate::serde::seq::strict) adapter.
+*/
+pub fn seq_strict_schema<B: Flags>(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": {
+            "type": "string",
+            "enum": item_names::<B>(),
+        },
+    })
+}
+
+/**
+A schema for a set of flags as a map of booleans, like `{ "A": true, "B": false }`.
+
+This describes the format used by the [`map`](crate::serde::map) adapter. Multi-bit fields
+are the name of one of their variants.
+*/
+pub fn map_schema<B: Flags>(_: &mut SchemaGenerator) -> Schema {
+    let mut properties = json_schema!({});
+
+    for flag in B::FLAGS.iter().filter(|flag| flag.is_named()) {
+        let property = if flag.is_field() {
+            json_schema!({
+                "type": "string",
+                "enum": flag.field_variants().collect::<Vec<_>>(),
+            })
+        } else {
+            json_schema!({ "type": "boolean" })
+        };
+
+        properties.insert(flag.display_name().into(), property.to_value());
+    }
+
+    json_schema!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+// A pattern matching the text format, like `A | B | 0x0c`
+//
+// Names are matched exactly, so the pattern is case-sensitive like the parser
+fn text_pattern<B: Flags>(numbers: bool) -> String {
+    let mut flag = String::new();
+
+    for defined in B::FLAGS.iter().filter(|flag| flag.is_named()) {
+        let names = names(defined);
+
+        if defined.is_field() {
+            let variants = defined.field_variants().collect::<Vec<_>>();
+
+            push_alternative(
+                &mut flag,
+                &format!(
+                    r"(?:{})\s*=\s*(?:{})",
+                    alternatives(&names),
+                    alternatives(&variants)
+                ),
+            );
+        } else {
+            push_alternative(&mut flag, &alternatives(&names));
+        }
+    }
+
+    if numbers {
+        push_alternative(&mut flag, NUMBER);
+    }
+
+    // If there's nothing to match then only empty text is valid
+    if flag.is_empty() {
+        return String::from(r"^\s*$");
+    }
+
+    format!(r"^\s*(?:(?:{0})(?:\s*\|\s*(?:{0}))*)?\s*$", flag)
+}
+
+// Each name a flag can be parsed from, without duplicates
+fn names<B>(flag: &crate::Flag<B>) -> Vec<&'static str> {
+    let mut names = Vec::new();
+
+    let all = [flag.name(), flag.display_name()]
+        .into_iter()
+        .chain(flag.aliases().iter().copied())
+        .chain(flag.deprecated_aliases().iter().copied());
+
+    for name in all {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+// The names of each item that can be written in a sequence of flags, like `A` or `MODE=Fast`
+fn item_names<B: Flags>() -> Vec<String> {
+    let mut items = Vec::new();
+
+    for flag in B::FLAGS.iter().filter(|flag| flag.is_named()) {
+        if flag.is_field() {
+            for variant in flag.field_variants() {
+                items.push(format!("{}={}", flag.display_name(), variant));
+            }
+        } else {
+            items.push(String::from(flag.display_name()));
+        }
+    }
+
+    items
+}
+
+// A pattern matching any of the given strings exactly
+fn alternatives(names: &[&str]) -> String {
+    let mut pattern = String::new();
+
+    for name in names {
+        push_alternative(&mut pattern, &escape(name));
+    }
+
+    pattern
+}
+
+fn push_alternative(pattern: &mut String, alternative: &str) {
+    if !pattern.is_empty() {
+        pattern.push('|');
+    }
+
+    pattern.push_str(alternative);
+}
+
+// Escape any characters that have a special meaning in a regular expression
+//
+// Schemas use ECMA-262 regular expressions, which don't allow escaping other characters
+fn escape(name: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in name.chars() {
+        if "\\^$.|?*+()[]{}/".contains(c) {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{TestField, TestFlags, TestNames};
+    use schemars::{generate::SchemaSettings, SchemaGenerator};
+
+    #[test]
+    fn schema() {
+        let schema = super::schema::<TestFlags>(&mut generator());
+
+        assert_eq!(
+            Some(
+                r"^\s*(?:(?:A|B|C|ABC|0x[0-9a-fA-F_]+|0b[01_]+|0o[0-7_]+|[0-9][0-9_]*)(?:\s*\|\s*(?:A|B|C|ABC|0x[0-9a-fA-F_]+|0b[01_]+|0o[0-7_]+|[0-9][0-9_]*))*)?\s*$"
+            ),
+            pattern(&schema)
+        );
+    }
+
+    #[test]
+    fn strict_schema() {
+        let schema = super::strict_schema::<TestField>(&mut generator());
+
+        assert_eq!(
+            Some(
+                r"^\s*(?:(?:A|(?:MODE)\s*=\s*(?:Slow|Fast|Turbo)|B)(?:\s*\|\s*(?:A|(?:MODE)\s*=\s*(?:Slow|Fast|Turbo)|B))*)?\s*$"
+            ),
+            pattern(&schema)
+        );
+
+        let schema = super::strict_schema::<TestNames>(&mut generator());
+
+        assert!(pattern(&schema)
+            .unwrap()
+            .starts_with(r"^\s*(?:(?:READ_ONLY|read-only|ro|READONLY|EXEC|exec|"));
+    }
+
+    #[test]
+    fn seq_schema() {
+        let schema = super::seq_strict_schema::<TestField>(&mut generator());
+
+        assert_eq!(
+            serde_json::json!({
+                "type": "array",
+                "items": {
+                    "type": "string",
+                    "enum": ["A", "MODE=Slow", "MODE=Fast", "MODE=Turbo", "B"],
+                },
+            }),
+            schema.to_value()
+        );
+
+        let schema = super::seq_schema::<TestFlags>(&mut generator());
+
+        assert_eq!(
+            Some(&serde_json::json!(["A", "B", "C", "ABC"])),
+            schema.pointer("/items/anyOf/0/enum")
+        );
+    }
+
+    #[test]
+    fn map_schema() {
+        let schema = super::map_schema::<TestField>(&mut generator());
+
+        assert_eq!(
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "A": { "type": "boolean" },
+                    "MODE": { "type": "string", "enum": ["Slow", "Fast", "Turbo"] },
+                    "B": { "type": "boolean" },
+                },
+                "additionalProperties": false,
+            }),
+            schema.to_value()
+        );
+    }
+
+    fn generator() -> SchemaGenerator {
+        SchemaGenerator::new(SchemaSettings::default())
+    }
+
+    fn pattern(schema: &schemars::Schema) -> Option<&str> {
+        schema.get("pattern").and_then(|pattern| pattern.as_str())
+    }
+}
@@ -1,7 +1,47 @@
-//! Specialized serialization for flags types using `serde`.
+/*!
+Specialized serialization for flags types using `serde`.
+
+The [`serialize`] and [`deserialize`] functions are used by generated flags types that derive
+`Serialize` and `Deserialize`. Human-readable formats use text, like `"A | B"`, and binary
+formats use the underlying bits value. Any unknown bits are retained.
+
+Other modules in this one can be used with `#[serde(with = ...)]` to change how unknown bits
+are handled, or to use another representation:
+
+- [`retain`] retains any unknown bits, like the functions at the root of this module.
+- [`truncate`] ignores any unknown bits.
+- [`strict`] fails to serialize or deserialize any unknown bits, so untrusted input can be checked:
+
+```
+use bitflags::bitflags;
+use serde_derive::{Deserialize, Serialize};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Request {
+    #[serde(with = "bitflags::serde::strict")]
+    permissions: Permissions,
+}
+
+assert!(serde_json::from_str::<Request>(r#"{"permissions":"READ | WRITE"}"#).is_ok());
+assert!(serde_json::from_str::<Request>(r#"{"permissions":"READ | 0x40"}"#).is_err());
+```
+
+- [`seq`] uses a sequence of flags, like `["A", "B"]`.
+- [`map`] uses a map of booleans, like `{ "A": true, "B": false }`.
+- [`any`] deserializes human-readable flags from either a string or an integer, and optionally a
+  sequence of flags.
+*/
 
 use crate::{
-    parser::{self, ParseHex, WriteHex},
+    parser::{self, ParseError, ParseHex, WriteHex},
     Flags,
 };
 use core::{fmt, str};
@@ -19,14 +59,7 @@ pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S:
 where
     B::Bits: WriteHex + Serialize,
 {
-    // Serialize human-readable flags as a string like `"A | B"`
-    if serializer.is_human_readable() {
-        serializer.collect_str(&parser::AsDisplay(flags))
-    }
-    // Serialize non-human-readable flags directly as the underlying bits
-    else {
-        flags.bits().serialize(serializer)
-    }
+    serialize_with::<B, S>(serializer, |f| parser::to_writer(flags, f), flags.bits())
 }
 
 /**
@@ -38,37 +71,969 @@ pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Resu
 where
     B::Bits: ParseHex + Deserialize<'de>,
 {
-    if deserializer.is_human_readable() {
-        // Deserialize human-readable flags by parsing them from strings like `"A | B"`
-        struct FlagsVisitor<B>(core::marker::PhantomData<B>);
+    deserialize_with(deserializer, parser::from_str, |bits| {
+        Some(B::from_bits_retain(bits))
+    })
+}
+
+pub mod retain {
+    /*!
+    Serializing flags values, retaining any unknown bits.
+
+    The functions in this module are the same as the ones in the [parent module](super).
+    */
+
+    use crate::{
+        parser::{ParseHex, WriteHex},
+        Flags,
+    };
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /**
+    Serialize a set of flags as a human-readable string or their underlying bits.
+
+    Any unknown bits will be retained.
+    */
+    pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: WriteHex + Serialize,
+    {
+        super::serialize(flags, serializer)
+    }
+
+    /**
+    Deserialize a set of flags from a human-readable string or their underlying bits.
+
+    Any unknown bits will be retained.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex + Deserialize<'de>,
+    {
+        super::deserialize(deserializer)
+    }
+}
+
+pub mod truncate {
+    /*!
+    Serializing flags values, ignoring any unknown bits.
+    */
+
+    use crate::{
+        parser::{self, ParseHex, WriteHex},
+        Flags,
+    };
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /**
+    Serialize a set of flags as a human-readable string or their underlying bits.
+
+    Any unknown bits will be ignored.
+    */
+    pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: WriteHex + Serialize,
+    {
+        super::serialize_with::<B, S>(
+            serializer,
+            |f| parser::to_writer_truncate(flags, f),
+            B::from_bits_truncate(flags.bits()).bits(),
+        )
+    }
+
+    /**
+    Deserialize a set of flags from a human-readable string or their underlying bits.
+
+    Any unknown bits will be ignored.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex + Deserialize<'de>,
+    {
+        super::deserialize_with(deserializer, parser::from_str_truncate, |bits| {
+            Some(B::from_bits_truncate(bits))
+        })
+    }
+}
+
+pub mod strict {
+    /*!
+    Serializing flags values, failing on any unknown bits.
+
+    Human-readable formats use the same text as the [parent module](super), so known bits
+    can still be written as hex values like `"A | 0x2"`. Any unknown bits, whether they're
+    written as hex values or underlying bits, are an error.
+    */
+
+    use crate::{
+        parser::{self, ParseHex, WriteHex},
+        Flags,
+    };
+    use serde::{ser::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    /**
+    Serialize a set of flags as a human-readable string or their underlying bits.
+
+    This function will fail on any unknown bits.
+    */
+    pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: WriteHex + Serialize,
+    {
+        if B::from_bits(flags.bits()).is_none() {
+            return Err(S::Error::custom("flags value contains unknown bits"));
+        }
+
+        super::serialize(flags, serializer)
+    }
+
+    /**
+    Deserialize a set of flags from a human-readable string or their underlying bits.
+
+    This function will fail on any unknown bits.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex + Deserialize<'de>,
+    {
+        super::deserialize_with(deserializer, parser::from_str, B::from_bits)
+    }
+}
+
+pub mod any {
+    /*!
+    Deserializing flags values from any of their human-readable representations.
+
+    The functions in this module can be used with `#[serde(with = "bitflags::serde::any")]` to
+    deserialize human-readable flags from either a string like `"A | B"`, or their underlying
+    bits as an integer. This can be useful when migrating documents that stored flags as
+    numbers. The [`with_seq`] module also accepts a sequence of flags, like `["A", "B"]`:
 
-        impl<'de, B: Flags> Visitor<'de> for FlagsVisitor<B>
+    ```
+    use bitflags::bitflags;
+    use serde_derive::{Deserialize, Serialize};
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Permissions: u8 {
+            const READ = 1;
+            const WRITE = 1 << 1;
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct File {
+        #[serde(with = "bitflags::serde::any::with_seq")]
+        permissions: Permissions,
+    }
+
+    let file = File {
+        permissions: Permissions::READ | Permissions::WRITE,
+    };
+
+    assert_eq!(file, serde_json::from_str(r#"{"permissions":"READ | WRITE"}"#).unwrap());
+    assert_eq!(file, serde_json::from_str(r#"{"permissions":3}"#).unwrap());
+    assert_eq!(file, serde_json::from_str(r#"{"permissions":["READ","WRITE"]}"#).unwrap());
+
+    // Flags are always serialized as a string
+    assert_eq!(r#"{"permissions":"READ | WRITE"}"#, serde_json::to_string(&file).unwrap());
+    ```
+
+    Integers are parsed like numbers in strings, so any reserved bits are fixed the same way, and
+    bits types wider than 64 bits can be deserialized from them. Deserializing any representation
+    needs a self-describing format, like JSON, so binary formats still use the underlying bits. Flags are always serialized like the [parent
+    module](super), and any unknown bits will be retained.
+    */
+
+    use crate::{
+        parser::{self, ParseHex, WriteHex},
+        Flags,
+    };
+    use core::{fmt, marker::PhantomData};
+    use serde::{
+        de::{Error, SeqAccess, Unexpected, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    /**
+    Serialize a set of flags as a human-readable string or their underlying bits.
+
+    Any unknown bits will be retained.
+    */
+    pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: WriteHex + Serialize,
+    {
+        super::serialize(flags, serializer)
+    }
+
+    /**
+    Deserialize a set of flags from a human-readable string or integer, or their underlying bits.
+
+    Any unknown bits will be retained.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex + Deserialize<'de>,
+    {
+        deserialize_any(deserializer, false)
+    }
+
+    pub mod with_seq {
+        /*!
+        Deserializing flags values from any of their human-readable representations, including
+        sequences of flags.
+        */
+
+        use crate::{
+            parser::{ParseHex, WriteHex},
+            Flags,
+        };
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        /**
+        Serialize a set of flags as a human-readable string or their underlying bits.
+
+        Any unknown bits will be retained.
+        */
+        pub fn serialize<B: Flags, S: Serializer>(
+            flags: &B,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
         where
-            B::Bits: ParseHex,
+            B::Bits: WriteHex + Serialize,
+        {
+            super::super::serialize(flags, serializer)
+        }
+
+        /**
+        Deserialize a set of flags from a human-readable string, integer, or sequence of flags,
+        or their underlying bits.
+
+        Any unknown bits will be retained.
+        */
+        pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<B, D::Error>
+        where
+            B::Bits: ParseHex + Deserialize<'de>,
+        {
+            super::deserialize_any(deserializer, true)
+        }
+    }
+
+    // Deserialize human-readable flags from whatever representation is in the input,
+    // accepting sequences if `seq` is set
+    fn deserialize_any<'de, B: Flags, D: Deserializer<'de>>(
+        deserializer: D,
+        seq: bool,
+    ) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex + Deserialize<'de>,
+    {
+        // Non-human-readable formats may not be self-describing
+        if !deserializer.is_human_readable() {
+            let bits = B::Bits::deserialize(deserializer)?;
+
+            return Ok(B::from_bits_retain(bits));
+        }
+
+        struct AnyVisitor<B> {
+            seq: bool,
+            _marker: PhantomData<B>,
+        }
+
+        impl<'de, B: Flags> Visitor<'de> for AnyVisitor<B>
+        where
+            B::Bits: ParseHex + Deserialize<'de>,
         {
             type Value = B;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str("a string value of `|` separated flags")
+                if self.seq {
+                    formatter.write_str(
+                        "a string value of `|` separated flags, an integer, or a sequence of flags",
+                    )
+                } else {
+                    formatter.write_str("a string value of `|` separated flags, or an integer")
+                }
             }
 
             fn visit_str<E: Error>(self, flags: &str) -> Result<Self::Value, E> {
                 parser::from_str(flags).map_err(|e| E::custom(e))
             }
+
+            fn visit_u64<E: Error>(self, bits: u64) -> Result<Self::Value, E> {
+                parse_integer(false, bits)
+                    .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(bits), &self))
+            }
+
+            fn visit_i64<E: Error>(self, bits: i64) -> Result<Self::Value, E> {
+                parse_integer(bits < 0, bits.unsigned_abs())
+                    .ok_or_else(|| E::invalid_value(Unexpected::Signed(bits), &self))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                if self.seq {
+                    super::seq::visit_items(seq, false)
+                } else {
+                    Err(A::Error::invalid_type(Unexpected::Seq, &self))
+                }
+            }
+        }
+
+        deserializer.deserialize_any(AnyVisitor {
+            seq,
+            _marker: PhantomData,
+        })
+    }
+
+    // Parse an integer as a hex number, like `0x1f` or `0x-1f`
+    //
+    // Integers are parsed like numbers in strings, so they're range checked and have their
+    // reserved bits fixed the same way, and bits types wider than 64 bits can be built from them
+    fn parse_integer<B: Flags>(negative: bool, magnitude: u64) -> Option<B>
+    where
+        B::Bits: ParseHex,
+    {
+        // `0x-` followed by at most 16 hex digits
+        let mut buf = [0; 19];
+        let mut start = buf.len();
+
+        let mut rest = magnitude;
+        loop {
+            start -= 1;
+            buf[start] = b"0123456789abcdef"[(rest & 0xf) as usize];
+            rest >>= 4;
+
+            if rest == 0 {
+                break;
+            }
+        }
+
+        if negative {
+            start -= 1;
+            buf[start] = b'-';
+        }
+
+        start -= 2;
+        buf[start..start + 2].copy_from_slice(b"0x");
+
+        parser::from_bytes(&buf[start..]).ok()
+    }
+}
+
+// Serialize human-readable flags as `text`, and non-human-readable flags as `bits`
+fn serialize_with<B: Flags, S: Serializer>(
+    serializer: S,
+    text: impl Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+    bits: B::Bits,
+) -> Result<S::Ok, S::Error>
+where
+    B::Bits: Serialize,
+{
+    struct Text<F>(F);
+
+    impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> fmt::Display for Text<F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.0)(f)
+        }
+    }
+
+    // Serialize human-readable flags as a string like `"A | B"`
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Text(text))
+    }
+    // Serialize non-human-readable flags directly as the underlying bits
+    else {
+        bits.serialize(serializer)
+    }
+}
+
+// Deserialize human-readable flags with `parse`, and check the bits of any flags with `from_bits`
+fn deserialize_with<'de, B: Flags, D: Deserializer<'de>>(
+    deserializer: D,
+    parse: fn(&str) -> Result<B, ParseError>,
+    from_bits: fn(B::Bits) -> Option<B>,
+) -> Result<B, D::Error>
+where
+    B::Bits: Deserialize<'de>,
+{
+    if deserializer.is_human_readable() {
+        // Deserialize human-readable flags by parsing them from strings like `"A | B"`
+        struct FlagsVisitor<B> {
+            parse: fn(&str) -> Result<B, ParseError>,
+        }
+
+        impl<'de, B: Flags> Visitor<'de> for FlagsVisitor<B> {
+            type Value = B;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string value of `|` separated flags")
+            }
+
+            fn visit_str<E: Error>(self, flags: &str) -> Result<Self::Value, E> {
+                (self.parse)(flags).map_err(|e| E::custom(e))
+            }
         }
 
-        deserializer.deserialize_str(FlagsVisitor(Default::default()))
+        let flags = deserializer.deserialize_str(FlagsVisitor { parse })?;
+
+        from_bits(flags.bits()).ok_or_else(|| D::Error::custom("flags value contains unknown bits"))
     } else {
         // Deserialize non-human-readable flags directly from the underlying bits
         let bits = B::Bits::deserialize(deserializer)?;
 
-        Ok(B::from_bits_retain(bits))
+        from_bits(bits).ok_or_else(|| D::Error::custom("flags value contains unknown bits"))
+    }
+}
+
+impl<B: Flags> Serialize for parser::Item<B>
+where
+    B::Bits: WriteHex,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+pub mod seq {
+    /*!
+    Serializing flags values as a sequence of flags.
+
+    The functions in this module can be used with `#[serde(with = "bitflags::serde::seq")]` to
+    serialize a flags value as a sequence of its flags, like `["A", "B"]`, instead of a string
+    like `"A | B"`. Each item in the sequence is a single flag, using the same grammar as
+    [`parser::from_names`](crate::parser::from_names):
+
+    ```
+    use bitflags::bitflags;
+    use serde_derive::{Deserialize, Serialize};
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Permissions: u8 {
+            const READ = 1;
+            const WRITE = 1 << 1;
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct File {
+        #[serde(with = "bitflags::serde::seq")]
+        permissions: Permissions,
+    }
+
+    let file = File {
+        permissions: Permissions::READ | Permissions::WRITE,
+    };
+
+    let json = serde_json::to_string(&file).unwrap();
+    assert_eq!(r#"{"permissions":["READ","WRITE"]}"#, json);
+
+    assert_eq!(file, serde_json::from_str(&json).unwrap());
+    ```
+
+    Unknown bits are retained as a hex number at the end of the sequence, like `["A", "0x40"]`.
+    The [`truncate`] module ignores them instead, and the [`strict`] module fails on them, like
+    the modules with the same names in the [parent module](super).
+    */
+
+    use crate::{
+        parser::{self, ParseHex, WriteHex},
+        Bits, Flags,
+    };
+    use core::{fmt, marker::PhantomData};
+    use serde::{
+        de::{DeserializeSeed, Error, SeqAccess, Visitor},
+        ser::SerializeSeq,
+        Deserializer, Serializer,
+    };
+
+    /**
+    Serialize a set of flags as a sequence of flags.
+
+    Any unknown bits will be retained.
+    */
+    pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: WriteHex,
+    {
+        serialize_items(flags, serializer, true)
+    }
+
+    /**
+    Deserialize a set of flags from a sequence of flags.
+
+    Any unknown bits will be retained.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex,
+    {
+        deserialize_items(deserializer, false)
+    }
+
+    pub mod truncate {
+        /*!
+        Serializing flags values as a sequence of flags, ignoring any unknown bits.
+        */
+
+        use crate::{
+            parser::{ParseHex, WriteHex},
+            Flags,
+        };
+        use serde::{Deserializer, Serializer};
+
+        /**
+        Serialize a set of flags as a sequence of flags.
+
+        Any unknown bits will be ignored.
+        */
+        pub fn serialize<B: Flags, S: Serializer>(
+            flags: &B,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            B::Bits: WriteHex,
+        {
+            super::serialize_items(&B::from_bits_truncate(flags.bits()), serializer, true)
+        }
+
+        /**
+        Deserialize a set of flags from a sequence of flags.
+
+        Any unknown bits will be ignored.
+        */
+        pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<B, D::Error>
+        where
+            B::Bits: ParseHex,
+        {
+            let flags: B = super::deserialize_items(deserializer, false)?;
+
+            Ok(B::from_bits_truncate(flags.bits()))
+        }
+    }
+
+    pub mod strict {
+        /*!
+        Serializing flags values as a sequence of only their named flags, failing on any unknown bits.
+        */
+
+        use crate::{
+            parser::{ParseHex, WriteHex},
+            Flags,
+        };
+        use serde::{ser::Error, Deserializer, Serializer};
+
+        /**
+        Serialize only the contained, defined, named flags in a set of flags as a sequence.
+
+        This function will fail on any unknown bits.
+        */
+        pub fn serialize<B: Flags, S: Serializer>(
+            flags: &B,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            B::Bits: WriteHex,
+        {
+            if B::from_bits(flags.bits()).is_none() {
+                return Err(S::Error::custom("flags value contains unknown bits"));
+            }
+
+            super::serialize_items(flags, serializer, false)
+        }
+
+        /**
+        Deserialize a set of flags from a sequence of flags.
+
+        This function will fail to deserialize numbers.
+        */
+        pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<B, D::Error>
+        where
+            B::Bits: ParseHex,
+        {
+            super::deserialize_items(deserializer, true)
+        }
+    }
+
+    // Serialize each flag, and any remaining bits if `retain` is set
+    fn serialize_items<B: Flags, S: Serializer>(
+        flags: &B,
+        serializer: S,
+        retain: bool,
+    ) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: WriteHex,
+    {
+        // Some formats need the length of a sequence up-front, so count the items first
+        let mut len = 0;
+        let remaining = match parser::for_each_item(flags, |_| {
+            len += 1;
+            Ok::<_, core::convert::Infallible>(())
+        }) {
+            Ok(remaining) => remaining,
+            Err(never) => match never {},
+        };
+
+        let retain = retain && remaining != B::Bits::EMPTY;
+        if retain {
+            len += 1;
+        }
+
+        let mut seq = serializer.serialize_seq(Some(len))?;
+
+        parser::for_each_item(flags, |item| seq.serialize_element(&item))?;
+
+        if retain {
+            seq.serialize_element(&parser::Item::<B>::Bits(remaining))?;
+        }
+
+        seq.end()
+    }
+
+    // Deserialize a sequence of flags, failing on any numbers if `strict` is set
+    fn deserialize_items<'de, B: Flags, D: Deserializer<'de>>(
+        deserializer: D,
+        strict: bool,
+    ) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex,
+    {
+        struct SeqVisitor<B> {
+            strict: bool,
+            _marker: PhantomData<B>,
+        }
+
+        impl<'de, B: Flags> Visitor<'de> for SeqVisitor<B>
+        where
+            B::Bits: ParseHex,
+        {
+            type Value = B;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence of flags")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                visit_items(seq, self.strict)
+            }
+        }
+
+        deserializer.deserialize_seq(SeqVisitor {
+            strict,
+            _marker: PhantomData,
+        })
+    }
+
+    // Parse each flag in a sequence, failing on any numbers if `strict` is set
+    pub(super) fn visit_items<'de, B: Flags, A: SeqAccess<'de>>(
+        mut seq: A,
+        strict: bool,
+    ) -> Result<B, A::Error>
+    where
+        B::Bits: ParseHex,
+    {
+        let mut parsed_flags = B::empty();
+
+        let mut index = 0;
+        while let Some(parsed_flag) = seq.next_element_seed(ItemSeed::<B> {
+            index,
+            strict,
+            _marker: PhantomData,
+        })? {
+            parsed_flags.insert(parsed_flag);
+            index += 1;
+        }
+
+        Ok(parser::fix_reserved(parsed_flags))
+    }
+
+    // Parses a single flag in a sequence
+    //
+    // Items are parsed as they're visited so they don't need to be borrowed or allocated
+    struct ItemSeed<B> {
+        index: usize,
+        strict: bool,
+        _marker: PhantomData<B>,
+    }
+
+    impl<'de, B: Flags> DeserializeSeed<'de> for ItemSeed<B>
+    where
+        B::Bits: ParseHex,
+    {
+        type Value = B;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<B, D::Error> {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    impl<'de, B: Flags> Visitor<'de> for ItemSeed<B>
+    where
+        B::Bits: ParseHex,
+    {
+        type Value = B;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a string value of a single flag")
+        }
+
+        fn visit_str<E: Error>(self, flag: &str) -> Result<Self::Value, E> {
+            parser::parse_item(flag, self.index, self.strict).map_err(|e| E::custom(e))
+        }
+    }
+}
+
+pub mod map {
+    /*!
+    Serializing flags values as a map of booleans.
+
+    The functions in this module can be used with `#[serde(with = "bitflags::serde::map")]` to
+    serialize a flags value as a map from the name of each defined flag to whether it's set, like
+    `{ "READ": true, "WRITE": false }`:
+
+    ```
+    use bitflags::bitflags;
+    use serde_derive::{Deserialize, Serialize};
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Permissions: u8 {
+            const READ = 1;
+            const WRITE = 1 << 1;
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct File {
+        #[serde(with = "bitflags::serde::map")]
+        permissions: Permissions,
+    }
+
+    let file = File {
+        permissions: Permissions::READ,
+    };
+
+    let json = serde_json::to_string(&file).unwrap();
+    assert_eq!(r#"{"permissions":{"READ":true,"WRITE":false}}"#, json);
+
+    assert_eq!(file, serde_json::from_str(&json).unwrap());
+    ```
+
+    Flags that are missing from the map, or that are `false`, are unset. Multi-bit fields are
+    mapped to the name of their variant instead of a boolean, like `{ "MODE": "Fast" }`.
+
+    A map can only contain defined flags, so any unknown bits are ignored. Deserializing will
+    fail on any keys that don't correspond to defined flags. The [`ignore_unknown`] module
+    ignores them instead.
+    */
+
+    use crate::{parser, Flag, Flags};
+    use core::{fmt, marker::PhantomData};
+    use serde::{
+        de::{DeserializeSeed, Error, IgnoredAny, MapAccess, Unexpected, Visitor},
+        ser::SerializeMap,
+        Deserializer, Serializer,
+    };
+
+    /**
+    Serialize a set of flags as a map of booleans.
+
+    Any unknown bits will be ignored.
+    */
+    pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries = || {
+            B::FLAGS
+                .iter()
+                .filter(|flag| flag.is_named())
+                .filter(|flag| !flag.is_field() || flag.field_variant(flags).is_some())
+        };
+
+        let mut map = serializer.serialize_map(Some(entries().count()))?;
+
+        for flag in entries() {
+            match flag.field_variant(flags) {
+                Some(variant) => map.serialize_entry(flag.display_name(), variant)?,
+                None => {
+                    let bits = flag.value().bits();
+
+                    map.serialize_entry(flag.display_name(), &(flags.bits() & bits == bits))?
+                }
+            }
+        }
+
+        map.end()
+    }
+
+    /**
+    Deserialize a set of flags from a map of booleans.
+
+    This function will fail on any keys that don't correspond to defined flags.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<B, D::Error> {
+        deserialize_entries(deserializer, false)
+    }
+
+    pub mod ignore_unknown {
+        /*!
+        Serializing flags values as a map of booleans, ignoring any unknown keys.
+        */
+
+        use crate::Flags;
+        use serde::{Deserializer, Serializer};
+
+        /**
+        Serialize a set of flags as a map of booleans.
+
+        Any unknown bits will be ignored.
+        */
+        pub fn serialize<B: Flags, S: Serializer>(
+            flags: &B,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            super::serialize(flags, serializer)
+        }
+
+        /**
+        Deserialize a set of flags from a map of booleans.
+
+        Any keys that don't correspond to defined flags will be ignored.
+        */
+        pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<B, D::Error> {
+            super::deserialize_entries(deserializer, true)
+        }
+    }
+
+    // Deserialize a map of flags, ignoring any unknown keys if `ignore_unknown` is set
+    fn deserialize_entries<'de, B: Flags, D: Deserializer<'de>>(
+        deserializer: D,
+        ignore_unknown: bool,
+    ) -> Result<B, D::Error> {
+        struct MapVisitor<B> {
+            ignore_unknown: bool,
+            _marker: PhantomData<B>,
+        }
+
+        impl<'de, B: Flags> Visitor<'de> for MapVisitor<B> {
+            type Value = B;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map of flags")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut parsed_flags = B::empty();
+
+                while let Some(flag) = map.next_key_seed(KeySeed::<B> {
+                    ignore_unknown: self.ignore_unknown,
+                    _marker: PhantomData,
+                })? {
+                    match flag {
+                        Some(flag) if flag.is_field() => {
+                            parsed_flags.insert(map.next_value_seed(VariantSeed(flag))?);
+                        }
+                        Some(flag) => {
+                            if map.next_value::<bool>()? {
+                                parsed_flags.insert(B::from_bits_retain(flag.value().bits()));
+                            }
+                        }
+                        None => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                Ok(parser::fix_reserved(parsed_flags))
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor {
+            ignore_unknown,
+            _marker: PhantomData,
+        })
+    }
+
+    // Finds the defined flag for a key in a map
+    //
+    // Unknown keys are `None` if `ignore_unknown` is set
+    struct KeySeed<B> {
+        ignore_unknown: bool,
+        _marker: PhantomData<B>,
+    }
+
+    impl<'de, B: Flags> DeserializeSeed<'de> for KeySeed<B> {
+        type Value = Option<&'static Flag<B>>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    impl<'de, B: Flags> Visitor<'de> for KeySeed<B> {
+        type Value = Option<&'static Flag<B>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("the name of a flag")
+        }
+
+        fn visit_str<E: Error>(self, name: &str) -> Result<Self::Value, E> {
+            match parser::find_flag(name) {
+                Ok(flag) => Ok(Some(flag)),
+                Err(_) if self.ignore_unknown => Ok(None),
+                Err(e) => Err(E::custom(e)),
+            }
+        }
+    }
+
+    // Parses the variant of a multi-bit field
+    struct VariantSeed<B: 'static>(&'static Flag<B>);
+
+    impl<'de, B: Flags> DeserializeSeed<'de> for VariantSeed<B> {
+        type Value = B;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<B, D::Error> {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    impl<'de, B: Flags> Visitor<'de> for VariantSeed<B> {
+        type Value = B;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                formatter,
+                "a variant of the `{}` field",
+                self.0.display_name()
+            )
+        }
+
+        fn visit_str<E: Error>(self, variant: &str) -> Result<Self::Value, E> {
+            self.0
+                .parse_field_variant(variant)
+                .ok_or_else(|| E::invalid_value(Unexpected::Str(variant), &self))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use serde_test::{assert_tokens, Configure, Token::*};
+    use serde_test::{
+        assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_ser_tokens_error,
+        assert_tokens, Compact, Configure, Readable, Token::*,
+    };
     bitflags! {
         #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
         #[serde(transparent)]
@@ -90,4 +1055,545 @@ mod tests {
 
         assert_tokens(&(SerdeFlags::A | SerdeFlags::B).compact(), &[U32(1 | 2)]);
     }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct RetainFlags(#[serde(with = "crate::serde::retain")] SerdeFlags);
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct TruncateFlags(#[serde(with = "crate::serde::truncate")] SerdeFlags);
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct StrictFlags(#[serde(with = "crate::serde::strict")] SerdeFlags);
+
+    #[test]
+    fn test_serde_bitflags_retain() {
+        let flags = || RetainFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40));
+
+        assert_tokens(&flags().readable(), &[Str("A | 0x40")]);
+
+        assert_tokens(&flags().compact(), &[U32(1 | 0x40)]);
+    }
+
+    #[test]
+    fn test_serde_bitflags_truncate() {
+        let flags = || TruncateFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40));
+
+        assert_ser_tokens(&flags().readable(), &[Str("A")]);
+        assert_de_tokens(&TruncateFlags(SerdeFlags::A).readable(), &[Str("A | 0x40")]);
+
+        assert_ser_tokens(&flags().compact(), &[U32(1)]);
+        assert_de_tokens(&TruncateFlags(SerdeFlags::A).compact(), &[U32(1 | 0x40)]);
+    }
+
+    #[test]
+    fn test_serde_bitflags_strict() {
+        let flags = || StrictFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40));
+
+        assert_tokens(
+            &StrictFlags(SerdeFlags::A | SerdeFlags::B).readable(),
+            &[Str("A | B")],
+        );
+        assert_de_tokens(
+            &StrictFlags(SerdeFlags::A | SerdeFlags::B).readable(),
+            &[Str("A | 0x2")],
+        );
+
+        assert_tokens(
+            &StrictFlags(SerdeFlags::A | SerdeFlags::B).compact(),
+            &[U32(1 | 2)],
+        );
+
+        assert_ser_tokens_error(
+            &flags().readable(),
+            &[],
+            "flags value contains unknown bits",
+        );
+        assert_ser_tokens_error(&flags().compact(), &[], "flags value contains unknown bits");
+
+        assert_de_tokens_error::<Readable<StrictFlags>>(
+            &[Str("A | 0x40")],
+            "flags value contains unknown bits",
+        );
+        assert_de_tokens_error::<Compact<StrictFlags>>(
+            &[U32(1 | 0x40)],
+            "flags value contains unknown bits",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_serde_bitflags_strict_invalid() {
+        assert_de_tokens_error::<Readable<StrictFlags>>(
+            &[Str("A | E")],
+            "unrecognized named flag `E` at 4..5, did you mean `A`?",
+        );
+    }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct AnyFlags(#[serde(with = "crate::serde::any")] SerdeFlags);
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct AnySeqFlags(#[serde(with = "crate::serde::any::with_seq")] SerdeFlags);
+
+    #[test]
+    fn test_serde_bitflags_any() {
+        let flags = || AnyFlags(SerdeFlags::A | SerdeFlags::B);
+
+        assert_tokens(&flags().readable(), &[Str("A | B")]);
+        assert_de_tokens(&flags().readable(), &[U64(1 | 2)]);
+        assert_de_tokens(&flags().readable(), &[U8(1 | 2)]);
+        assert_de_tokens(&flags().readable(), &[I64(1 | 2)]);
+
+        assert_tokens(&flags().compact(), &[U32(1 | 2)]);
+
+        assert_de_tokens(
+            &AnyFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40)).readable(),
+            &[U64(1 | 0x40)],
+        );
+
+        assert_de_tokens(
+            &AnySeqFlags(SerdeFlags::A | SerdeFlags::B).readable(),
+            &[
+                Seq {
+                    len: Option::Some(2),
+                },
+                Str("A"),
+                Str("B"),
+                SeqEnd,
+            ],
+        );
+        assert_de_tokens(
+            &AnySeqFlags(SerdeFlags::A | SerdeFlags::B).readable(),
+            &[U64(1 | 2)],
+        );
+    }
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        struct ReservedFlags: u8 {
+            const A = 1;
+
+            #[reserved(zero)]
+            const _ = 1 << 7;
+
+            #[reserved(one)]
+            const _ = 1 << 6;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct SignedFlags: i8 {
+            const A = 1;
+        }
+    }
+
+    #[derive(serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct AnyReservedFlags(#[serde(with = "crate::serde::any")] ReservedFlags);
+
+    #[derive(serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct AnySignedFlags(#[serde(with = "crate::serde::any")] SignedFlags);
+
+    #[derive(serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct AnyWideFlags(#[serde(with = "crate::serde::any")] WideFlags);
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct WideFlags(crate::Words<2>);
+
+    impl crate::Flags for WideFlags {
+        const FLAGS: &'static [crate::Flag<Self>] = &[
+            crate::Flag::new("LOW", WideFlags(crate::Words::bit(0))),
+            crate::Flag::new("HIGH", WideFlags(crate::Words::bit(127))),
+        ];
+
+        type Bits = crate::Words<2>;
+
+        fn bits(&self) -> crate::Words<2> {
+            self.0
+        }
+
+        fn from_bits_retain(bits: crate::Words<2>) -> Self {
+            WideFlags(bits)
+        }
+    }
+
+    #[test]
+    fn test_serde_bitflags_any_integers() {
+        // Integers have their reserved bits fixed like strings do
+        assert_de_tokens(
+            &AnyReservedFlags(ReservedFlags::from_bits_retain(0b0100_0001)).readable(),
+            &[U64(0b1000_0001)],
+        );
+        assert_de_tokens(
+            &AnyReservedFlags(ReservedFlags::from_bits_retain(0b0100_0001)).readable(),
+            &[Str("A | 0x80")],
+        );
+
+        assert_de_tokens(
+            &AnySignedFlags(SignedFlags::from_bits_retain(-1)).readable(),
+            &[I64(-1)],
+        );
+        assert_de_tokens(
+            &AnySignedFlags(SignedFlags::from_bits_retain(-128)).readable(),
+            &[I64(-128)],
+        );
+        assert_de_tokens_error::<Readable<AnySignedFlags>>(
+            &[I64(-129)],
+            "invalid value: integer `-129`, expected a string value of `|` separated flags, \
+             or an integer",
+        );
+
+        // Bits types wider than 64 bits can still be built from integers
+        assert_de_tokens(
+            &AnyWideFlags(WideFlags(crate::Words::from_words([u64::MAX, 0]))).readable(),
+            &[U64(u64::MAX)],
+        );
+        assert_de_tokens(
+            &AnyWideFlags(WideFlags(crate::Words::bit(0))).readable(),
+            &[I64(1)],
+        );
+    }
+
+    #[test]
+    fn test_serde_bitflags_any_invalid() {
+        assert_de_tokens_error::<Readable<AnyFlags>>(
+            &[U64(1 << 32)],
+            "invalid value: integer `4294967296`, expected a string value of `|` separated flags, \
+             or an integer",
+        );
+
+        assert_de_tokens_error::<Readable<AnyFlags>>(
+            &[I64(-1)],
+            "invalid value: integer `-1`, expected a string value of `|` separated flags, \
+             or an integer",
+        );
+
+        assert_de_tokens_error::<Readable<AnyFlags>>(
+            &[Seq {
+                len: Option::Some(1),
+            }],
+            "invalid type: sequence, expected a string value of `|` separated flags, or an integer",
+        );
+
+        assert_de_tokens_error::<Readable<AnySeqFlags>>(
+            &[Bool(true)],
+            "invalid type: boolean `true`, expected a string value of `|` separated flags, \
+             an integer, or a sequence of flags",
+        );
+    }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct SeqFlags(#[serde(with = "crate::serde::seq")] SerdeFlags);
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct SeqTruncateFlags(#[serde(with = "crate::serde::seq::truncate")] SerdeFlags);
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct SeqStrictFlags(#[serde(with = "crate::serde::seq::strict")] SerdeFlags);
+
+    #[test]
+    fn test_serde_bitflags_seq() {
+        assert_tokens(
+            &SeqFlags(SerdeFlags::empty()),
+            &[
+                Seq {
+                    len: Option::Some(0),
+                },
+                SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &SeqFlags(SerdeFlags::A | SerdeFlags::B),
+            &[
+                Seq {
+                    len: Option::Some(2),
+                },
+                Str("A"),
+                Str("B"),
+                SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &SeqFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40)),
+            &[
+                Seq {
+                    len: Option::Some(2),
+                },
+                Str("A"),
+                Str("0x40"),
+                SeqEnd,
+            ],
+        );
+
+        assert_de_tokens(
+            &SeqFlags(SerdeFlags::A | SerdeFlags::C | SerdeFlags::from_bits_retain(0x40)),
+            &[
+                Seq { len: Option::None },
+                Str(" C "),
+                Str("A"),
+                Str("0x40"),
+                SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_serde_bitflags_seq_truncate() {
+        assert_ser_tokens(
+            &SeqTruncateFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40)),
+            &[
+                Seq {
+                    len: Option::Some(1),
+                },
+                Str("A"),
+                SeqEnd,
+            ],
+        );
+
+        assert_de_tokens(
+            &SeqTruncateFlags(SerdeFlags::A),
+            &[
+                Seq {
+                    len: Option::Some(2),
+                },
+                Str("A"),
+                Str("0x40"),
+                SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_serde_bitflags_seq_strict() {
+        assert_tokens(
+            &SeqStrictFlags(SerdeFlags::A | SerdeFlags::B),
+            &[
+                Seq {
+                    len: Option::Some(2),
+                },
+                Str("A"),
+                Str("B"),
+                SeqEnd,
+            ],
+        );
+
+        assert_ser_tokens_error(
+            &SeqStrictFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40)),
+            &[],
+            "flags value contains unknown bits",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_serde_bitflags_seq_invalid() {
+        assert_de_tokens_error::<SeqStrictFlags>(
+            &[
+                Seq {
+                    len: Option::Some(2),
+                },
+                Str("A"),
+                Str("0x40"),
+            ],
+            "invalid number flag `unsupported number flag value` at 0..4 in item 1",
+        );
+
+        assert_de_tokens_error::<SeqFlags>(
+            &[
+                Seq {
+                    len: Option::Some(2),
+                },
+                Str("A"),
+                Str("E"),
+            ],
+            "unrecognized named flag `E` at 0..1 in item 1, did you mean `A`?",
+        );
+
+        assert_de_tokens_error::<SeqFlags>(
+            &[
+                Seq {
+                    len: Option::Some(1),
+                },
+                Str("A | B"),
+            ],
+            "unrecognized named flag `A | B` at 0..5 in item 0",
+        );
+
+        assert_de_tokens_error::<SeqFlags>(
+            &[Str("A")],
+            "invalid type: string \"A\", expected a sequence of flags",
+        );
+    }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct MapFlags(#[serde(with = "crate::serde::map")] SerdeFlags);
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct MapIgnoreUnknownFlags(#[serde(with = "crate::serde::map::ignore_unknown")] SerdeFlags);
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    struct MapFieldFlags(#[serde(with = "crate::serde::map")] crate::tests::TestField);
+
+    #[test]
+    fn test_serde_bitflags_map() {
+        assert_tokens(
+            &MapFlags(SerdeFlags::A | SerdeFlags::C),
+            &[
+                Map {
+                    len: Option::Some(4),
+                },
+                Str("A"),
+                Bool(true),
+                Str("B"),
+                Bool(false),
+                Str("C"),
+                Bool(true),
+                Str("D"),
+                Bool(false),
+                MapEnd,
+            ],
+        );
+
+        assert_ser_tokens(
+            &MapFlags(SerdeFlags::A | SerdeFlags::from_bits_retain(0x40)),
+            &[
+                Map {
+                    len: Option::Some(4),
+                },
+                Str("A"),
+                Bool(true),
+                Str("B"),
+                Bool(false),
+                Str("C"),
+                Bool(false),
+                Str("D"),
+                Bool(false),
+                MapEnd,
+            ],
+        );
+
+        // Missing and `false` keys are unset
+        assert_de_tokens(
+            &MapFlags(SerdeFlags::B),
+            &[
+                Map { len: Option::None },
+                Str("A"),
+                Bool(false),
+                Str("B"),
+                Bool(true),
+                MapEnd,
+            ],
+        );
+
+        assert_de_tokens(
+            &MapIgnoreUnknownFlags(SerdeFlags::B),
+            &[
+                Map { len: Option::None },
+                Str("B"),
+                Bool(true),
+                Str("E"),
+                Bool(true),
+                MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_serde_bitflags_map_field() {
+        use crate::{
+            tests::{TestField, TestMode},
+            Flags,
+        };
+
+        let mut flags = TestField::A;
+        flags.set_field(TestMode::Turbo);
+
+        assert_tokens(
+            &MapFieldFlags(flags),
+            &[
+                Map {
+                    len: Option::Some(3),
+                },
+                Str("A"),
+                Bool(true),
+                Str("MODE"),
+                Str("Turbo"),
+                Str("B"),
+                Bool(false),
+                MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_serde_bitflags_map_formats() {
+        let flags = MapFlags(SerdeFlags::A | SerdeFlags::D);
+
+        let json = serde_json::to_string(&flags).unwrap();
+        assert_eq!(r#"{"A":true,"B":false,"C":false,"D":true}"#, json);
+        assert_eq!(flags, serde_json::from_str(&json).unwrap());
+
+        let toml = toml::to_string(&Config {
+            flags: SerdeFlags::A | SerdeFlags::D,
+        })
+        .unwrap();
+        assert_eq!("[flags]\nA = true\nB = false\nC = false\nD = true\n", toml);
+        assert_eq!(flags.0, toml::from_str::<Config>(&toml).unwrap().flags);
+
+        #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::serde::map")]
+            flags: SerdeFlags,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_serde_bitflags_map_invalid() {
+        assert_de_tokens_error::<MapFlags>(
+            &[
+                Map {
+                    len: Option::Some(1),
+                },
+                Str("E"),
+            ],
+            "unrecognized named flag `E` at 0..1, did you mean `A`?",
+        );
+
+        assert_de_tokens_error::<MapFlags>(
+            &[
+                Map {
+                    len: Option::Some(1),
+                },
+                Str("A"),
+                Str("true"),
+            ],
+            "invalid type: string \"true\", expected a boolean",
+        );
+
+        assert_de_tokens_error::<MapFieldFlags>(
+            &[
+                Map {
+                    len: Option::Some(1),
+                },
+                Str("MODE"),
+                Str("Warp"),
+            ],
+            "invalid value: string \"Warp\", expected a variant of the `MODE` field",
+        );
+    }
 }
@@ -0,0 +1,106 @@
+/*!
+Specialized zero-copy parsing for flags types using `zerocopy`.
+
+`zerocopy` traits can only be derived, so flags types generated by the [`bitflags`](crate::bitflags)
+macro don't implement them. Instead, declare the flags type yourself, derive `IntoBytes`,
+`FromBytes`, `KnownLayout`, and `Immutable` on it, and then use the macro to generate its methods.
+The derives refer to your own `zerocopy` dependency, so they need the `derive` feature of
+`zerocopy`, and `#[zerocopy(crate = "...")]` if you've renamed it:
+
+```
+use bitflags::bitflags;
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+#[derive(IntoBytes, FromBytes, KnownLayout, Immutable, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+struct Header(u16);
+
+bitflags! {
+    impl Header: u16 {
+        const SYN = 1;
+        const ACK = 1 << 1;
+    }
+}
+
+let header = Header::SYN | Header::ACK;
+
+assert_eq!(header, Header::read_from_bytes(header.as_bytes()).unwrap());
+```
+
+Since flags values can retain unknown bits, any bits are a valid flags value, and methods like
+`FromBytes::ref_from_bytes` never check them. The functions in this module only succeed if no
+unknown bits are set, like [`Flags::from_bits`], so untrusted input can be checked:
+
+```
+use bitflags::{bitflags, zerocopy as strict};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+#[derive(IntoBytes, FromBytes, KnownLayout, Immutable, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+struct Header(u16);
+
+bitflags! {
+    impl Header: u16 {
+        const SYN = 1;
+        const ACK = 1 << 1;
+    }
+}
+
+let bytes = 0b11u16.to_ne_bytes();
+assert_eq!(Some(&(Header::SYN | Header::ACK)), strict::try_ref_from_bytes(&bytes));
+
+let bytes = 0b111u16.to_ne_bytes();
+assert_eq!(None, strict::try_ref_from_bytes::<Header>(&bytes));
+```
+
+# `TryFromBytes`
+
+This module doesn't support `TryFromBytes` with a validity check. `zerocopy` only supports
+deriving `TryFromBytes`, and its derive can't run a custom check like rejecting unknown bits.
+Flags types that derive `FromBytes` also implement `TryFromBytes`, but methods like
+`TryFromBytes::try_ref_from_bytes` always succeed for them, even if unknown bits are set. Use the
+functions in this module for strict reads instead.
+*/
+
+use crate::Flags;
+use zerocopy::{FromBytes, Immutable, KnownLayout};
+
+/**
+Read a flags value from bytes.
+
+This function will return `None` if the length of `bytes` isn't the size of the flags value,
+or if any unknown bits are set.
+*/
+pub fn try_read_from_bytes<B: Flags + FromBytes>(bytes: &[u8]) -> Option<B> {
+    B::read_from_bytes(bytes)
+        .ok()
+        .filter(|flags| !flags.contains_unknown_bits())
+}
+
+/**
+Interpret bytes as a flags value.
+
+This function will return `None` if the length or alignment of `bytes` don't match the flags
+value, or if any unknown bits are set.
+*/
+pub fn try_ref_from_bytes<B: Flags + FromBytes + KnownLayout + Immutable>(
+    bytes: &[u8],
+) -> Option<&B> {
+    B::ref_from_bytes(bytes)
+        .ok()
+        .filter(|flags| !flags.contains_unknown_bits())
+}
+
+/**
+Interpret bytes as a slice of flags values.
+
+This function will return `None` if the length or alignment of `bytes` don't match a slice of
+flags values, or if any unknown bits are set in any of them.
+*/
+pub fn try_ref_slice_from_bytes<B: Flags + FromBytes + KnownLayout + Immutable>(
+    bytes: &[u8],
+) -> Option<&[B]> {
+    <[B]>::ref_from_bytes(bytes)
+        .ok()
+        .filter(|flags| !flags.iter().any(|flags| flags.contains_unknown_bits()))
+}
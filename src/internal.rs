@@ -47,13 +47,18 @@ macro_rules! __impl_internal_bitflags {
         impl $crate::__private::core::default::Default for $InternalBitFlags {
             #[inline]
             fn default() -> Self {
-                $InternalBitFlags::empty()
+                // Set any reserved bits that must be set, so the default is always a valid value
+                $InternalBitFlags::from_bits_retain(<$PublicBitFlags as $crate::Flags>::RESERVED_ONE)
             }
         }
 
         impl $crate::__private::core::fmt::Debug for $InternalBitFlags {
             fn fmt(&self, f: &mut $crate::__private::core::fmt::Formatter<'_>) -> $crate::__private::core::fmt::Result {
-                if self.is_empty() {
+                // Reserved bits are never formatted, so they don't count here
+                let reserved = <$PublicBitFlags as $crate::Flags>::RESERVED_ZERO
+                    | <$PublicBitFlags as $crate::Flags>::RESERVED_ONE;
+
+                if self.bits() & !reserved == <$T as $crate::Bits>::EMPTY {
                     // If no flags are set then write an empty hex flag to avoid
                     // writing an empty string. In some contexts, like serialization,
                     // an empty string is preferable, but it may be unexpected in
@@ -104,10 +104,9 @@ impl<B: 'static> IterNames<B> {
     }
 }
 
-impl<B: Flags> Iterator for IterNames<B> {
-    type Item = (&'static str, B);
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl<B: Flags> IterNames<B> {
+    // Like `next`, but yields the defined flag instead of its name
+    pub(crate) fn next_flag(&mut self) -> Option<(&'static Flag<B>, B)> {
         while let Some(flag) = self.flags.get(self.idx) {
             // Short-circuit if our state is empty
             if self.remaining.is_empty() {
@@ -136,10 +135,18 @@ impl<B: Flags> Iterator for IterNames<B> {
             {
                 self.remaining.remove(B::from_bits_retain(bits));
 
-                return Some((flag.name(), B::from_bits_retain(bits)));
+                return Some((flag, B::from_bits_retain(bits)));
             }
         }
 
         None
     }
 }
+
+impl<B: Flags> Iterator for IterNames<B> {
+    type Item = (&'static str, B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_flag().map(|(flag, value)| (flag.name(), value))
+    }
+}
@@ -65,16 +65,51 @@ without generating additional constants for them. It helps compatibility when th
 may start setting additional bits at any time. The [known and unknown bits](#known-and-unknown-bits)
 section has more details on this behavior.
 
+### Large flags types
+
+The `bitflags` macro supports primitive integers up to `u128` as bits types. For flags types
+that need more bits, the [`Words`] type stores bits in an array of `u64` words and can be used
+as the bits type instead. Flags are defined using its `const` methods, like [`Words::bit`].
+
+### Sharing flags values between threads
+
+With the `atomic` feature enabled, the [`atomic::Atomic`](atomic/struct.Atomic.html) type can be
+used to share a flags value between threads, inserting, removing, and toggling flags without a lock.
+The `atomic` feature needs at least Rust 1.60, which is newer than the rest of the crate.
+
+The [`event::EventGroup`](event/struct.EventGroup.html) type can be used to wait until flags are
+set by other threads or tasks through a future, which doesn't need `std`. With the `std` feature
+also enabled, it can block the current thread too.
+
+### Memory-mapped registers
+
+With the `volatile` feature enabled, the [`volatile::VolatileFlags`](volatile/struct.VolatileFlags.html)
+type reads and writes flags values in memory-mapped registers using volatile operations, with
+support for write-1-to-clear and write-1-to-set bits.
+
+### Encoding flags values as bytes
+
+The [`bytes`] module encodes and decodes flags values as little-endian or big-endian bytes,
+optionally numbering bits from the most significant for protocols that do.
+
 ### Custom derives
 
 You can derive some traits on generated flags types if you enable Cargo features. The following
 libraries are currently supported:
 
 - `serde`: Support `#[derive(Serialize, Deserialize)]`, using text for human-readable formats,
-  and a raw number for binary formats.
+  and a raw number for binary formats. The `serde` module also has adapters for
+  `#[serde(with = ...)]` that reject or ignore unknown bits, or serialize flags as a sequence of
+  names or a map of booleans.
 - `arbitrary`: Support `#[derive(Arbitrary)]`, only generating flags values with known bits.
 - `bytemuck`: Support `#[derive(Pod, Zeroable)]`, for casting between flags values and their
-  underlying bits values.
+  underlying bits values. The `bytemuck` module can also reject any unknown bits while casting.
+- `schemars`: Support `#[derive(JsonSchema)]`, describing the text format used by `serde` for
+  human-readable formats. This feature needs at least Rust 1.74, which is newer than the rest of
+  the crate, because `schemars` 1.0 does.
+- `zerocopy`: Support zero-copy parsing of flags values that rejects any unknown bits, with the
+  `zerocopy` module. `zerocopy` traits can only be derived, so they aren't added to flags types
+  generated by the [`bitflags`] macro. Derive them on your own flags type instead, as shown below.
 
 You can also define your own flags type outside of the [`bitflags`] macro and then use it to generate methods.
 This can be useful if you need a custom `#[derive]` attribute for a library that `bitflags` doesn't
@@ -239,16 +274,36 @@ The result of `Flags::A ^ Flags::B` is `0b0000_0010`, which doesn't correspond t
 */
 
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
-#![cfg_attr(not(test), forbid(unsafe_code))]
+// Only the opt-in `atomic`, `bytemuck`, and `volatile` features need `unsafe` code
+#![cfg_attr(
+    not(any(test, feature = "atomic", feature = "bytemuck", feature = "volatile")),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(
+    all(
+        not(test),
+        any(feature = "atomic", feature = "bytemuck", feature = "volatile")
+    ),
+    deny(unsafe_code)
+)]
 #![cfg_attr(test, allow(mixed_script_confusables))]
 
+#[cfg(any(feature = "alloc", feature = "schemars"))]
+extern crate alloc;
+
+#[doc(inline)]
+pub use traits::{Bits, Field, FieldValue, Flag, Flags};
+
 #[doc(inline)]
-pub use traits::{Bits, Flag, Flags};
+pub use words::Words;
 
+pub mod bytes;
 pub mod iter;
 pub mod parser;
 
+mod const_parser;
 mod traits;
+mod words;
 
 #[doc(hidden)]
 pub mod __private {
@@ -256,6 +311,8 @@ pub mod __private {
     // Easier than conditionally checking any optional external dependencies
     pub use crate::{external::__private::*, traits::__private::*};
 
+    pub use crate::const_parser::{ConstError, ConstFlag, ConstParser};
+
     pub use core;
 }
 
@@ -442,6 +499,237 @@ bitflags! {
     }
 }
 ```
+
+# Reserved bits
+
+Unnamed flags may be marked as reserved bits that must always be unset with `#[reserved(zero)]`,
+or always set with `#[reserved(one)]`. This is useful for hardware registers and wire formats.
+
+Reserved bits that must be unset are never known bits, so [`Flags::from_bits`] rejects
+values with them set, and truncating operators like [`Flags::from_bits_truncate`] and `!` unset them.
+Reserved bits that must be set are always known, so [`Flags::from_bits`] rejects values with them
+unset, and truncating operators set them. The [`parser`] never formats reserved bits, and ignores
+them when parsing.
+
+`empty()` always unsets all bits, including reserved bits that must be set, so it doesn't return
+a valid value for flags types with `#[reserved(one)]` bits. `Default` sets them, so it returns
+the smallest valid value, like `from_bits_truncate(0)`.
+
+Any other argument to the `#[reserved]` attribute is a compile error. So is using it on a named
+flag, because reserved bits are never named:
+
+```compile_fail
+# use bitflags::bitflags;
+bitflags! {
+    struct Flags: u8 {
+        #[reserved(zero)]
+        const A = 1;
+    }
+}
+```
+
+## Examples
+
+Declaring a flags type where all bits are known except for reserved ones:
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+
+        #[reserved(zero)]
+        const _ = 1 << 7;
+
+        #[reserved(one)]
+        const _ = 1 << 6;
+
+        const _ = !0;
+    }
+}
+
+assert_eq!(0b0111_1111, Flags::all().bits());
+assert_eq!(None, Flags::from_bits(0b1100_0001));
+assert_eq!(None, Flags::from_bits(0b0000_0001));
+assert_eq!(Some(Flags::from_bits_retain(0b0100_0001)), Flags::from_bits(0b0100_0001));
+
+assert_eq!(0b0100_0001, Flags::from_bits_truncate(0b1000_0001).bits());
+assert_eq!(0b0111_1110, (!Flags::from_bits_retain(0b0100_0001)).bits());
+
+// Empty values don't set reserved bits that must be set, but default values do
+assert_eq!(None, Flags::from_bits(Flags::empty().bits()));
+assert_eq!(0b0100_0000, Flags::from_bits_truncate(0).bits());
+assert_eq!(Flags::from_bits_truncate(0), Flags::default());
+```
+
+# Fields
+
+Named multi-bit flags may be declared as fields with `#[field(Type)]`, where `Type` implements
+[`FieldValue`] for the bits type. The value of the flag is the mask of bits in the field, and
+the value of the field is shifted into the lowest set bit of the mask.
+
+The macro implements [`Field`] for `Type`, so the field can be read and written with
+[`Flags::get_field`] and [`Flags::set_field`]. The [`parser`] formats fields using the name
+of their variant, like `MODE=Fast`.
+
+Each type can only be used for a single field in a flags type. The mask of a field can't be
+empty, which is checked when the flags type is defined. This check needs at least Rust 1.57.
+
+The name of a field refers to its mask, like its associated constant, so [`Flags::from_name`]
+and the [`parser`] return a value with all bits of the field set when given the name of a field
+on its own.
+
+## Examples
+
+Declaring a 2-bit field between two flags:
+
+```
+use bitflags::{bitflags, FieldValue, Flags};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Slow = 0,
+    Fast = 1,
+    Turbo = 2,
+}
+
+impl FieldValue<u8> for Mode {
+    const VARIANTS: &'static [(&'static str, Mode)] = &[
+        ("Slow", Mode::Slow),
+        ("Fast", Mode::Fast),
+        ("Turbo", Mode::Turbo),
+    ];
+
+    fn to_bits(&self) -> u8 {
+        *self as u8
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Register: u8 {
+        const ENABLE = 1;
+
+        #[field(Mode)]
+        const MODE = 0b0000_0110;
+
+        const IRQ = 1 << 3;
+    }
+}
+
+let mut register = Register::ENABLE;
+register.set_field(Mode::Fast);
+
+assert_eq!(0b0000_0011, register.bits());
+assert_eq!(Some(Mode::Fast), register.get_field::<Mode>());
+
+assert_eq!("Register(MODE=Fast | ENABLE)", format!("{:?}", register));
+assert_eq!(register, bitflags::parser::from_str("ENABLE | MODE=Fast").unwrap());
+```
+
+A field with an empty mask fails the build:
+
+```compile_fail,E0080
+use bitflags::{bitflags, FieldValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Slow = 0,
+}
+
+impl FieldValue<u8> for Mode {
+    const VARIANTS: &'static [(&'static str, Mode)] = &[("Slow", Mode::Slow)];
+
+    fn to_bits(&self) -> u8 {
+        *self as u8
+    }
+}
+
+bitflags! {
+    struct Register: u8 {
+        #[field(Mode)]
+        const MODE = 0;
+    }
+}
+```
+
+# Display names and aliases
+
+Named flags may be given a different name to use when formatting as text with `#[display("name")]`,
+which doesn't need to be a Rust identifier. Display names are used by `Display`, `Debug`, and
+[`parser::to_writer`], so they can't be empty, start with a digit, or contain whitespace, `|`, or
+`=`, or they couldn't be parsed again. This is checked when the flags type is defined, which
+needs at least Rust 1.57. Other names to accept when parsing may be given with
+`#[alias("name")]`, or `#[alias("name", deprecated)]` for names that should eventually stop being
+accepted. Flags are always parsed from their name too. These names are available through
+[`Flag::display_name`], [`Flag::aliases`], and [`Flag::deprecated_aliases`].
+
+## Examples
+
+```
+use bitflags::{bitflags, parser::{self, ParserOptions}};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Permissions: u8 {
+        #[display("read-only")]
+        #[alias("ro")]
+        #[alias("READONLY", deprecated)]
+        const READ_ONLY = 1;
+
+        #[display("exec")]
+        const EXEC = 1 << 1;
+    }
+}
+
+assert_eq!("Permissions(read-only | exec)", format!("{:?}", Permissions::all()));
+
+for name in ["READ_ONLY", "read-only", "ro", "READONLY"] {
+    assert_eq!(Permissions::READ_ONLY, parser::from_str(name).unwrap());
+}
+
+let options = ParserOptions::new().deprecated_aliases(false);
+assert!(parser::from_str_with::<Permissions>("READONLY", &options).is_err());
+```
+
+Display names may contain `-`, even when parsing expressions like `all - read-only`, because
+defined names are preferred over splitting them:
+
+```
+use bitflags::{bitflags, parser::{self, ParserOptions}};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Permissions: u8 {
+        #[display("read-only")]
+        const READ_ONLY = 1;
+
+        const EXEC = 1 << 1;
+    }
+}
+
+let debug = format!("{:?}", Permissions::all());
+assert_eq!("Permissions(read-only | EXEC)", debug);
+
+let options = ParserOptions::new().type_name("Permissions").expressions(true);
+assert_eq!(Permissions::all(), parser::from_str_with(&debug, &options).unwrap());
+assert_eq!(Permissions::EXEC, parser::from_str_with("all - read-only", &options).unwrap());
+```
+
+A display name that couldn't be parsed again fails the build:
+
+```compile_fail,E0080
+use bitflags::bitflags;
+
+bitflags! {
+    struct Permissions: u8 {
+        #[display("read only")]
+        const READ_ONLY = 1;
+    }
+}
+```
 */
 #[macro_export]
 macro_rules! bitflags {
@@ -890,6 +1178,117 @@ macro_rules! __bitflags_match {
     }
 }
 
+/// A macro that parses a flags value from text at compile time.
+///
+/// The text uses the same format as [`parser::from_str`], and is parsed into a constant,
+/// so it doesn't cost anything at runtime. Any text that can't be parsed fails the build.
+///
+/// # Syntax
+///
+/// ```ignore
+/// flags!(FlagsType, "A | B | 0x10")
+/// ```
+///
+/// The flags type must be generated by the [`bitflags`] macro, and the text must be a string literal.
+/// Names may be the name of a flag, its display name, or any of its aliases. Fields, like `MODE=Fast`,
+/// can't be parsed at compile time. Parsing at compile time needs at least Rust 1.57.
+///
+/// The error for text that can't be parsed names the flag that caused it and its byte range in
+/// the text, like ``failed to parse `A | C`: unrecognized named flag `C` at 4..5``. Compilers
+/// before Rust 1.63 only give the kind of error.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitflags::{bitflags, flags};
+///
+/// bitflags! {
+///     #[derive(Debug, PartialEq, Eq)]
+///     struct Flags: u8 {
+///         const A = 1 << 0;
+///         const B = 1 << 1;
+///     }
+/// }
+///
+/// const DEFAULT: Flags = flags!(Flags, "A | B | 0x10");
+///
+/// assert_eq!(Flags::A | Flags::B | Flags::from_bits_retain(0x10), DEFAULT);
+/// ```
+///
+/// Text that can't be parsed fails the build:
+///
+/// ```compile_fail,E0080
+/// use bitflags::{bitflags, flags};
+///
+/// bitflags! {
+///     struct Flags: u8 {
+///         const A = 1 << 0;
+///     }
+/// }
+///
+/// const DEFAULT: Flags = flags!(Flags, "A | C");
+/// ```
+#[macro_export]
+macro_rules! flags {
+    ($Flags:ty, $input:literal $(,)?) => {{
+        const VALUE: $Flags = {
+            let flags = <$Flags as $crate::Flags>::FLAGS;
+
+            let mut parser = $crate::__private::ConstParser::new(
+                $input,
+                $crate::__private::core::mem::size_of::<$Flags>(),
+            );
+            let mut bits = <$Flags>::empty().bits();
+
+            loop {
+                let (next, flag) = parser.next(flags);
+                parser = next;
+
+                match flag {
+                    $crate::__private::ConstFlag::End => break,
+                    $crate::__private::ConstFlag::Named(i) => {
+                        bits = $crate::__private::ConstBits::<
+                            <$Flags as $crate::Flags>::Bits,
+                        >::union(bits, flags[i].value().bits())
+                    }
+                    $crate::__private::ConstFlag::Bits(value) => {
+                        bits = $crate::__private::ConstBits::<
+                            <$Flags as $crate::Flags>::Bits,
+                        >::union(
+                            bits,
+                            $crate::__private::ConstBits::<<$Flags as $crate::Flags>::Bits>::from_u128(value),
+                        )
+                    }
+                    $crate::__private::ConstFlag::Error(kind) => {
+                        let error = parser.error::<{ $input.len() * 2 + 96 }>(kind);
+
+                        $crate::__private::core::panic!("{}", error.as_str())
+                    }
+                }
+            }
+
+            // Unset any reserved bits that must be unset, and set any that must be set
+            let reserved_zero = <$Flags as $crate::Flags>::RESERVED_ZERO;
+            let reserved_one = <$Flags as $crate::Flags>::RESERVED_ONE;
+
+            <$Flags>::from_bits_retain($crate::__private::ConstBits::<
+                <$Flags as $crate::Flags>::Bits,
+            >::union(
+                $crate::__private::ConstBits::<<$Flags as $crate::Flags>::Bits>::difference(
+                    bits,
+                    $crate::__private::ConstBits::<<$Flags as $crate::Flags>::Bits>::union(
+                        reserved_zero,
+                        reserved_one,
+                    ),
+                ),
+                reserved_one,
+            ))
+        };
+
+        VALUE
+    }};
+}
+
 /// A macro that processed the input to `bitflags!` and shuffles attributes around
 /// based on whether or not they're "expression-safe".
 ///
@@ -987,6 +1386,519 @@ macro_rules! __bitflags_expr_safe_attrs {
     }
 }
 
+/// A macro that expands to a block if a flag has a `#[reserved(zero)]` or `#[reserved(one)]`
+/// attribute matching the given kind.
+///
+/// This macro is a token-tree muncher that looks through each attribute on a flag. Any
+/// expression-safe attributes, like `cfg`, are applied to the block.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_reserved {
+    // Entrypoint: Move all attributes into an `unprocessed` list
+    // where they'll be munched one-at-a-time
+    (
+        $kind:ident,
+        $(#[$inner:ident $($args:tt)*])*
+        { $($block:tt)* }
+    ) => {
+        $crate::__bitflags_reserved! {
+            kind: $kind,
+            block: { $($block)* },
+            attrs: {
+                unprocessed: [$(#[$inner $($args)*])*],
+                all: [$(#[$inner $($args)*])*],
+            },
+        }
+    };
+    // The next attribute is `#[reserved(zero)]` and we're looking for it
+    (
+        kind: zero,
+        block: { $($block:tt)* },
+        attrs: {
+            unprocessed: [#[reserved(zero)] $($attrs_rest:tt)*],
+            all: [$($all:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_expr_safe_attrs!(
+            $($all)*
+            { $($block)* }
+        )
+    };
+    // The next attribute is `#[reserved(one)]` and we're looking for it
+    (
+        kind: one,
+        block: { $($block:tt)* },
+        attrs: {
+            unprocessed: [#[reserved(one)] $($attrs_rest:tt)*],
+            all: [$($all:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_expr_safe_attrs!(
+            $($all)*
+            { $($block)* }
+        )
+    };
+    // The next attribute is `#[reserved(one)]` but we're looking for `#[reserved(zero)]`
+    (
+        kind: zero,
+        block: { $($block:tt)* },
+        attrs: {
+            unprocessed: [#[reserved(one)] $($attrs_rest:tt)*],
+            all: [$($all:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_reserved! {
+            kind: zero,
+            block: { $($block)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                all: [$($all)*],
+            },
+        }
+    };
+    // The next attribute is a malformed `#[reserved]` attribute
+    //
+    // This is only checked when looking for `#[reserved(zero)]` so the error is only reported once
+    (
+        kind: zero,
+        block: { $($block:tt)* },
+        attrs: {
+            unprocessed: [#[reserved $($args:tt)*] $($attrs_rest:tt)*],
+            all: [$($all:tt)*],
+        },
+    ) => {
+        $crate::__private::core::compile_error!($crate::__private::core::concat!(
+            "unrecognized attribute `#[reserved",
+            $crate::__private::core::stringify!($($args)*),
+            "]`, expected `#[reserved(zero)]` or `#[reserved(one)]`"
+        ));
+    };
+    // The next attribute is something else, so skip it
+    (
+        kind: $kind:ident,
+        block: { $($block:tt)* },
+        attrs: {
+            unprocessed: [#[$other:ident $($args:tt)*] $($attrs_rest:tt)*],
+            all: [$($all:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_reserved! {
+            kind: $kind,
+            block: { $($block)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                all: [$($all)*],
+            },
+        }
+    };
+    // No matching attribute was found
+    (
+        kind: $kind:ident,
+        block: { $($block:tt)* },
+        attrs: {
+            unprocessed: [],
+            all: [$($all:tt)*],
+        },
+    ) => {};
+}
+
+/// A macro that generates the parts of a flag that depend on whether it has a `#[field(Type)]`
+/// attribute.
+///
+/// This macro is a token-tree muncher that looks through each attribute on a flag, sorting them
+/// into expression-safe attributes, like `cfg`, all attributes that aren't specific to `bitflags`,
+/// and the type of the field, if any. The `kind` determines what's generated from them:
+///
+/// - `const`: The associated constant for the flag, without any `#[field]` attribute.
+/// - `flag`: The entry for the flag in `Flags::FLAGS`.
+/// - `impl`: The implementation of `Field` for the type of the field, if any, and a check of the
+///   flag's display name.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_field {
+    // Entrypoint: Move all attributes into an `unprocessed` list
+    // where they'll be munched one-at-a-time
+    (
+        $kind:ident { $($ctx:tt)* }
+        $(#[$inner:ident $($args:tt)*])*
+    ) => {
+        $crate::__bitflags_field! {
+            kind: $kind { $($ctx)* },
+            attrs: {
+                unprocessed: [$(#[$inner $($args)*])*],
+                cfg: [],
+                other: [],
+                field: [],
+                names: {
+                    display: [],
+                    aliases: [],
+                    deprecated: [],
+                },
+            },
+        }
+    };
+    // The next attribute is `#[field(Type)]`
+    (
+        kind: $kind:ident { $($ctx:tt)* },
+        attrs: {
+            unprocessed: [#[field($Field:ty)] $($attrs_rest:tt)*],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$($field:tt)*],
+            names: $names:tt,
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: $kind { $($ctx)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                cfg: [$($cfg)*],
+                other: [$($other)*],
+                field: [$Field],
+                names: $names,
+            },
+        }
+    };
+    // The next attribute is `cfg`, which is also propagated to expressions
+    (
+        kind: $kind:ident { $($ctx:tt)* },
+        attrs: {
+            unprocessed: [#[cfg $($args:tt)*] $($attrs_rest:tt)*],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$($field:tt)*],
+            names: $names:tt,
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: $kind { $($ctx)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                cfg: [$($cfg)* #[cfg $($args)*]],
+                other: [$($other)* #[cfg $($args)*]],
+                field: [$($field)*],
+                names: $names,
+            },
+        }
+    };
+    // The next attribute is `#[display("name")]`
+    (
+        kind: $kind:ident { $($ctx:tt)* },
+        attrs: {
+            unprocessed: [#[display($display:literal)] $($attrs_rest:tt)*],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$($field:tt)*],
+            names: {
+                display: [$($prev:tt)*],
+                aliases: [$($aliases:tt)*],
+                deprecated: [$($deprecated:tt)*],
+            },
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: $kind { $($ctx)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                cfg: [$($cfg)*],
+                other: [$($other)*],
+                field: [$($field)*],
+                names: {
+                    display: [$display],
+                    aliases: [$($aliases)*],
+                    deprecated: [$($deprecated)*],
+                },
+            },
+        }
+    };
+    // The next attribute is `#[alias("name", deprecated)]`
+    (
+        kind: $kind:ident { $($ctx:tt)* },
+        attrs: {
+            unprocessed: [#[alias($alias:literal, deprecated)] $($attrs_rest:tt)*],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$($field:tt)*],
+            names: {
+                display: [$($display:tt)*],
+                aliases: [$($aliases:tt)*],
+                deprecated: [$($deprecated:tt)*],
+            },
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: $kind { $($ctx)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                cfg: [$($cfg)*],
+                other: [$($other)*],
+                field: [$($field)*],
+                names: {
+                    display: [$($display)*],
+                    aliases: [$($aliases)*],
+                    deprecated: [$($deprecated)* $alias,],
+                },
+            },
+        }
+    };
+    // The next attribute is `#[alias("name")]`
+    (
+        kind: $kind:ident { $($ctx:tt)* },
+        attrs: {
+            unprocessed: [#[alias($alias:literal)] $($attrs_rest:tt)*],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$($field:tt)*],
+            names: {
+                display: [$($display:tt)*],
+                aliases: [$($aliases:tt)*],
+                deprecated: [$($deprecated:tt)*],
+            },
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: $kind { $($ctx)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                cfg: [$($cfg)*],
+                other: [$($other)*],
+                field: [$($field)*],
+                names: {
+                    display: [$($display)*],
+                    aliases: [$($aliases)* $alias,],
+                    deprecated: [$($deprecated)*],
+                },
+            },
+        }
+    };
+    // The next attribute is `#[reserved]`, which can only be used on unnamed flags
+    //
+    // This is only checked when generating the associated constant so the error is only reported
+    // once. The constant is still generated without the attribute to avoid any other errors
+    (
+        kind: const { $PublicBitFlags:ident, $Flag:ident = $value:expr },
+        attrs: {
+            unprocessed: [#[reserved $($args:tt)*] $($attrs_rest:tt)*],
+            $($attrs:tt)*
+        },
+    ) => {
+        $crate::__private::core::compile_error!($crate::__private::core::concat!(
+            "`#[reserved",
+            $crate::__private::core::stringify!($($args)*),
+            "]` can only be used on unnamed flags like `const _`, not on `",
+            $crate::__private::core::stringify!($Flag),
+            "`"
+        ));
+
+        $crate::__bitflags_field! {
+            kind: const { $PublicBitFlags, $Flag = $value },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                $($attrs)*
+            },
+        }
+    };
+    // The next attribute is something else
+    (
+        kind: $kind:ident { $($ctx:tt)* },
+        attrs: {
+            unprocessed: [#[$next:ident $($args:tt)*] $($attrs_rest:tt)*],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$($field:tt)*],
+            names: $names:tt,
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: $kind { $($ctx)* },
+            attrs: {
+                unprocessed: [$($attrs_rest)*],
+                cfg: [$($cfg)*],
+                other: [$($other)* #[$next $($args)*]],
+                field: [$($field)*],
+                names: $names,
+            },
+        }
+    };
+    // Generate the associated constant for the flag
+    (
+        kind: const { $PublicBitFlags:ident, $Flag:ident = $value:expr },
+        attrs: {
+            unprocessed: [],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$($field:tt)*],
+            names: $names:tt,
+        },
+    ) => {
+        $($other)*
+        #[allow(
+            deprecated,
+            non_upper_case_globals,
+        )]
+        pub const $Flag: $PublicBitFlags = $PublicBitFlags::from_bits_retain($value);
+    };
+    // Generate the entry in `Flags::FLAGS` for a regular flag
+    (
+        kind: flag { $PublicBitFlags:ident, $Flag:ident },
+        attrs: {
+            unprocessed: [],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [],
+            names: {
+                display: [$($display:literal)*],
+                aliases: [$($aliases:tt)*],
+                deprecated: [$($deprecated:tt)*],
+            },
+        },
+    ) => {
+        {
+            #[allow(
+                deprecated,
+                non_upper_case_globals,
+            )]
+            $crate::Flag::new($crate::__private::core::stringify!($Flag), $PublicBitFlags::$Flag)
+                $(.with_display_name($display))*
+                .with_aliases(&[$($aliases)*])
+                .with_deprecated_aliases(&[$($deprecated)*])
+        }
+    };
+    // Generate the entry in `Flags::FLAGS` for a field
+    (
+        kind: flag { $PublicBitFlags:ident, $Flag:ident },
+        attrs: {
+            unprocessed: [],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$Field:ty],
+            names: {
+                display: [$($display:literal)*],
+                aliases: [$($aliases:tt)*],
+                deprecated: [$($deprecated:tt)*],
+            },
+        },
+    ) => {
+        {
+            #[allow(
+                deprecated,
+                non_upper_case_globals,
+            )]
+            $crate::__private::FieldFlag::<$PublicBitFlags, $Field>::FLAG
+                $(.with_display_name($display))*
+                .with_aliases(&[$($aliases)*])
+                .with_deprecated_aliases(&[$($deprecated)*])
+        }
+    };
+    // Regular flags don't implement `Field`
+    (
+        kind: impl { $PublicBitFlags:ident: $T:ty, $Flag:ident },
+        attrs: {
+            unprocessed: [],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [],
+            names: $names:tt,
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: names { $Flag },
+            cfg: [$($cfg)*],
+            names: $names,
+        }
+    };
+    // Implement `Field` for the type of a field
+    (
+        kind: impl { $PublicBitFlags:ident: $T:ty, $Flag:ident },
+        attrs: {
+            unprocessed: [],
+            cfg: [$($cfg:tt)*],
+            other: [$($other:tt)*],
+            field: [$Field:ty],
+            names: $names:tt,
+        },
+    ) => {
+        $crate::__bitflags_field! {
+            kind: names { $Flag },
+            cfg: [$($cfg)*],
+            names: $names,
+        }
+
+        // Check the mask of the field when the flags type is defined
+        $($cfg)*
+        #[allow(
+            deprecated,
+            non_upper_case_globals,
+        )]
+        const _: () = {
+            if $crate::__private::ConstBits::<$T>::eq(
+                $PublicBitFlags::$Flag.bits(),
+                <$T as $crate::Bits>::EMPTY,
+            ) {
+                $crate::__private::core::panic!($crate::__private::core::concat!(
+                    "the mask of field `",
+                    $crate::__private::core::stringify!($Flag),
+                    "` must not be empty"
+                ))
+            }
+        };
+
+        $($cfg)*
+        #[allow(
+            deprecated,
+            non_upper_case_globals,
+        )]
+        impl $crate::Field<$PublicBitFlags> for $Field {
+            const NAME: &'static str = $crate::__private::core::stringify!($Flag);
+
+            const MASK: $PublicBitFlags = $PublicBitFlags::$Flag;
+
+            fn from_flags(flags: &$PublicBitFlags) -> $crate::__private::core::option::Option<Self> {
+                <Self as $crate::FieldValue<$T>>::from_bits(
+                    $crate::__private::ConstBits::<$T>::field_from(
+                        flags.bits(),
+                        $PublicBitFlags::$Flag.bits(),
+                    ),
+                )
+            }
+
+            fn to_flags(&self) -> $PublicBitFlags {
+                $PublicBitFlags::from_bits_retain(
+                    $crate::__private::ConstBits::<$T>::field_to(
+                        <Self as $crate::FieldValue<$T>>::to_bits(self),
+                        $PublicBitFlags::$Flag.bits(),
+                    ),
+                )
+            }
+        }
+    };
+    // Check the display name of a flag when the flags type is defined
+    (
+        kind: names { $Flag:ident },
+        cfg: [$($cfg:tt)*],
+        names: {
+            display: [$($display:literal)*],
+            aliases: $aliases:tt,
+            deprecated: $deprecated:tt,
+        },
+    ) => {
+        $($cfg)*
+        const _: () = {
+            $(
+                if !$crate::__private::is_valid_display_name($display) {
+                    $crate::__private::core::panic!($crate::__private::core::concat!(
+                        "the display name `",
+                        $display,
+                        "` of flag `",
+                        $crate::__private::core::stringify!($Flag),
+                        "` must not be empty, start with a digit, or contain whitespace, `|`, or `=`"
+                    ))
+                }
+            )*
+        };
+    };
+}
+
 /// Implement a flag, which may be a wildcard `_`.
 #[macro_export]
 #[doc(hidden)]
@@ -1018,6 +1930,15 @@ mod internal;
 #[macro_use]
 mod external;
 
+#[cfg(feature = "atomic")]
+pub mod atomic;
+
+#[cfg(feature = "atomic")]
+pub mod event;
+
+#[cfg(feature = "volatile")]
+pub mod volatile;
+
 #[cfg(feature = "example_generated")]
 pub mod example_generated;
 
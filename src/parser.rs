@@ -4,9 +4,16 @@ Parsing flags from text.
 Format and parse a flags value as text using the following grammar:
 
 - _Flags:_ (_Whitespace_ _Flag_ _Whitespace_)`|`*
-- _Flag:_ _Name_ | _Hex Number_
+- _Flag:_ _Name_ | _Field_ | _Number_
 - _Name:_ The name of any defined flag
-- _Hex Number_: `0x`([0-9a-fA-F])*
+- _Field:_ _Name_ _Whitespace_ `=` _Whitespace_ _Variant_
+- _Variant:_ The name of any variant of a field
+- _Number:_ _Hex Number_ | _Binary Number_ | _Octal Number_ | _Decimal Number_
+- _Hex Number_: `0x`_Sign_([0-9a-fA-F_])*
+- _Binary Number_: `0b`_Sign_(\[01_\])*
+- _Octal Number_: `0o`_Sign_([0-7_])*
+- _Decimal Number_: [0-9]([0-9_])*
+- _Sign_: (`+` | `-`)?
 - _Whitespace_: (\s)*
 
 As an example, this is how `Flags::A | Flags::B | 0x0c` can be represented as text:
@@ -21,25 +28,186 @@ Alternatively, it could be represented without whitespace:
 A|B|0x0C
 ```
 
+Numbers may use `_` to separate digits, like `0b1010_0000`. For compatibility with earlier
+versions, prefixed numbers may also have a sign, like `0x+1`, and a `-` sign is accepted for
+signed bits types. Unknown bits are formatted as hex numbers by default, but may be formatted
+in another [`Radix`] with [`to_writer_with`].
+
+Names are formatted in the order their flags are declared, skipping any flags whose bits have
+already been formatted. With [`ParserOptions::canonical`], the smallest set of names covering
+the flags value is formatted instead, so `RWX` is preferred over `R | W | X`.
+
+Text that's in a byte slice, like in a binary protocol, can be parsed with [`from_bytes`] without
+validating that it's UTF-8 first. [`from_bytes_with`] accepts the same [`ParserOptions`] as
+[`from_str_with`].
+
+Flags values can also be parsed from a sequence of individual flags, like `["A", "B", "0x0c"]`,
+with [`from_names`].
+
 Note that identifiers are *case-sensitive*, so the following is *not equivalent*:
 
 ```text
 a|b|0x0C
 ```
+
+Multi-bit fields, declared with a `#[field(Type)]` attribute, are formatted before any other
+flags using the name of their variant, like `MODE=Fast | A`. Fields with no bits set aren't
+formatted.
+
+Reserved bits, declared by [`Flags::RESERVED_ZERO`] and [`Flags::RESERVED_ONE`], are never
+formatted, and are ignored in any parsed hex numbers. Parsed flags values always have their
+reserved bits that must be set, set.
+
+## Source expressions
+
+Flags values can also be written as expressions in source code, for code generators.
+[`to_writer_rust`] writes a Rust expression, and [`to_writer_c`] writes a C expression:
+
+```
+use bitflags::{bitflags, parser};
+
+bitflags! {
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+let flags = Flags::A | Flags::B | Flags::from_bits_retain(0x40);
+
+let mut rust = String::new();
+parser::to_writer_rust(&flags, &mut rust, "Flags").unwrap();
+
+assert_eq!("Flags::A | Flags::B | Flags::from_bits_retain(0x40)", rust);
+
+let mut c = String::new();
+parser::to_writer_c(&flags, &mut c, "FLAG_").unwrap();
+
+assert_eq!("FLAG_A | FLAG_B | 0x40", c);
+```
+
+Expressions only contain flag names and numbers, so the bits of fields are written as the
+name of the field if all of its bits are set, and as a number otherwise.
+
+## Expressions
+
+[`from_str_with`] can optionally parse each _Flag_ as an expression, with
+[`ParserOptions::expressions`], using the following grammar:
+
+- _Expression:_ _Operand_ (_Whitespace_ `-` _Whitespace_ _Operand_)*
+- _Operand:_ `!` _Whitespace_ _Operand_ | `all` | `empty` | `none` | _Flag_
+
+`all` is all known bits, `empty` and `none` are no bits, `!` is the complement, and `-` is the
+difference. Defined flags take precedence over the `all`, `empty` and `none` keywords, and over
+`-`, so names like `read-only` are never split. As an example, this is how all flags except
+`Flags::DEBUG` can be represented as text:
+
+```text
+all - DEBUG
+```
+
+Expressions are only parsed. Formatting always uses the grammar above, which is a subset of
+the grammar with expressions, so formatted text can always be parsed again.
+
+## Type names
+
+[`from_str_with`] can optionally accept the name of the flags type, with
+[`ParserOptions::type_name`], so the output of `Debug` and [`to_writer_rust`] can be parsed:
+
+- The input may be wrapped in the name of the type, like `MyFlags(A | B)`.
+- Flags may be qualified by the type or a path to it, like `MyFlags::A` or `crate::MyFlags::A`.
+- Qualified flags may also be `empty()` or `from_bits_retain(`_Number_`)`.
+
+`Debug` writes the display name of each flag, while [`to_writer_rust`] writes the name of its
+constant. Both are accepted when parsing.
+
+```
+use bitflags::{bitflags, parser::{self, ParserOptions}};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct MyFlags: u8 {
+        const A = 1;
+
+        #[display("b")]
+        const B = 1 << 1;
+    }
+}
+
+let options = ParserOptions::new().type_name("MyFlags");
+let flags = MyFlags::A | MyFlags::B;
+
+assert_eq!("MyFlags(A | b)", format!("{:?}", flags));
+assert_eq!(flags, parser::from_str_with(&format!("{:?}", flags), &options).unwrap());
+assert_eq!(flags, parser::from_str_with("MyFlags::A | MyFlags::B", &options).unwrap());
+```
+
+## Errors
+
+Errors from parsing carry the [`ParseErrorKind`], the byte range of the flag in the input that
+caused them, and for unrecognized names, the name of the nearest defined flag if one looks like
+a likely typo:
+
+```
+use bitflags::{bitflags, parser};
+
+bitflags! {
+    #[derive(Debug)]
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+    }
+}
+
+let err = parser::from_str::<Permissions>("READ | WRTE").unwrap_err();
+
+assert_eq!(Some(7..11), err.span());
+assert_eq!(Some("WRITE"), err.suggestion());
+```
+
+With the `alloc` feature enabled, errors also retain the text of the flag that caused them,
+and [`from_str_collect_errors`](fn.from_str_collect_errors.html) can report every invalid flag
+in the input instead of stopping at the first. `ParseError` implements `core::error::Error` on
+compilers that support it, and `std::error::Error` on older compilers with the `std` feature
+enabled.
 */
 
 #![allow(clippy::let_unit_value)]
 
 use core::fmt::{self, Write};
 
-use crate::{Bits, Flags};
+use core::ops::Range;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::{self, Vec},
+};
+
+use crate::{Bits, Flag, Flags};
 
 /**
 Write a flags value as text.
 
 Any bits that aren't part of a contained flag will be formatted as a hex number.
 */
-pub fn to_writer<B: Flags>(flags: &B, mut writer: impl Write) -> Result<(), fmt::Error>
+pub fn to_writer<B: Flags>(flags: &B, writer: impl Write) -> Result<(), fmt::Error>
+where
+    B::Bits: WriteHex,
+{
+    to_writer_with(flags, writer, &ParserOptions::new())
+}
+
+/**
+Write a flags value as text, using the separator and radix from the given options.
+
+Any bits that aren't part of a contained flag will be formatted as a number.
+*/
+pub fn to_writer_with<B: Flags>(
+    flags: &B,
+    mut writer: impl Write,
+    options: &ParserOptions<'_>,
+) -> Result<(), fmt::Error>
 where
     B::Bits: WriteHex,
 {
@@ -51,89 +219,458 @@ where
     // followed by a hex number of any remaining bits that are set
     // but don't correspond to any flags.
 
-    // Iterate over known flag values
+    // Write any fields first
     let mut first = true;
-    let mut iter = flags.iter_names();
-    for (name, _) in &mut iter {
-        if !first {
-            writer.write_str(" | ")?;
+    let (flags, unknown_fields) = write_fields(flags, &mut first, &mut writer, options)?;
+
+    // Iterate over known flag values
+    let remaining = if options.canonical {
+        write_canonical(&flags, &mut first, &mut writer, options)?
+    } else {
+        let mut iter = flags.iter_names();
+        while let Some((flag, _)) = iter.next_flag() {
+            if !first {
+                writer.write_str(options.write_separator)?;
+            }
+
+            first = false;
+            writer.write_str(flag.display_name())?;
         }
 
-        first = false;
-        writer.write_str(name)?;
-    }
+        iter.remaining().bits()
+    };
 
     // Append any extra bits that correspond to flags to the end of the format
-    let remaining = iter.remaining().bits();
+    // Reserved bits are never formatted
+    // Fields that don't correspond to any variant are formatted as part of the hex number
+    let remaining = (remaining | unknown_fields) & !reserved::<B>();
     if remaining != B::Bits::EMPTY {
         if !first {
-            writer.write_str(" | ")?;
+            writer.write_str(options.write_separator)?;
         }
 
-        writer.write_str("0x")?;
-        remaining.write_hex(writer)?;
+        remaining.write_radix(writer, options.write_radix)?;
     }
 
     fmt::Result::Ok(())
 }
 
-#[cfg(feature = "serde")]
-pub(crate) struct AsDisplay<'a, B>(pub(crate) &'a B);
+/**
+Parse a flags value from text.
 
-#[cfg(feature = "serde")]
-impl<'a, B: Flags> fmt::Display for AsDisplay<'a, B>
+This function will fail on any names that don't correspond to defined flags.
+Unknown bits will be retained.
+*/
+pub fn from_str<B: Flags>(input: &str) -> Result<B, ParseError>
 where
-    B::Bits: WriteHex,
+    B::Bits: ParseHex,
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        to_writer(self.0, f)
-    }
+    from_str_with(input, &ParserOptions::new())
 }
 
 /**
-Parse a flags value from text.
+Parse a flags value from text, using the grammar from the given options.
 
 This function will fail on any names that don't correspond to defined flags.
 Unknown bits will be retained.
 */
-pub fn from_str<B: Flags>(input: &str) -> Result<B, ParseError>
+pub fn from_str_with<B: Flags>(input: &str, options: &ParserOptions<'_>) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    // Stop at the first error
+    parse(input, options, &parse_number, Err)
+}
+
+/**
+Parse a flags value from text, using the grammar from the given options, and collecting
+all errors instead of stopping at the first.
+
+This function will fail on any names that don't correspond to defined flags.
+Unknown bits will be retained.
+*/
+#[cfg(feature = "alloc")]
+pub fn from_str_collect_errors<B: Flags>(
+    input: &str,
+    options: &ParserOptions<'_>,
+) -> Result<B, ParseErrors>
 where
     B::Bits: ParseHex,
 {
+    let mut errors = Vec::new();
+
+    let parsed_flags = parse(input, options, &parse_number, |err| {
+        errors.push(err);
+        Ok(())
+    })
+    .map_err(|err| ParseErrors(alloc::vec![err]))?;
+
+    if errors.is_empty() {
+        Ok(parsed_flags)
+    } else {
+        Err(ParseErrors(errors))
+    }
+}
+
+// Parse flags from text, passing any errors to `report`
+//
+// Parsing continues after an error if `report` returns `Ok`. Numbers are parsed by
+// `parse_number`, which is given the whole input, the flag, its radix, and its digits
+fn parse<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    options: &ParserOptions<'_>,
+    parse_number: &impl Fn(&T, &T, Radix, &T) -> Result<B, ParseError>,
+    mut report: impl FnMut(ParseError) -> Result<(), ParseError>,
+) -> Result<B, ParseError> {
     let mut parsed_flags = B::empty();
 
+    // If the input is wrapped in the name of the type then parse its contents
+    // Errors are still reported relative to the whole input
+    let mut flags = strip_type_wrapper(input, options).unwrap_or(input);
+
     // If the input is empty then return an empty set of flags
-    if input.trim().is_empty() {
-        return Ok(parsed_flags);
+    if flags.trim().is_empty() {
+        return Ok(fix_reserved(parsed_flags));
     }
 
-    for flag in input.split('|') {
+    loop {
+        let (flag, rest) = match flags.split_once_any(options.separators) {
+            Some((flag, rest)) => (flag, Some(rest)),
+            None => (flags, None),
+        };
         let flag = flag.trim();
 
         // If the flag is empty then we've got missing input
+        // This is allowed after a trailing separator if the options support it
         if flag.is_empty() {
-            return Err(ParseError::empty_flag());
+            if options.trailing_separator && rest.is_none() {
+                break;
+            }
+
+            report(ParseError::empty_flag().with_span(span(input, flag)))?;
+        } else if options.whitespace_separators {
+            let mut rest = flag;
+            while !rest.is_empty() {
+                let (flag, remaining) = split_whitespace(rest, options);
+                rest = remaining;
+
+                match parse_flag(input, flag, options, parse_number) {
+                    Ok(parsed_flag) => parsed_flags.insert(parsed_flag),
+                    Err(err) => report(err)?,
+                }
+            }
+        } else {
+            match parse_flag(input, flag, options, parse_number) {
+                Ok(parsed_flag) => parsed_flags.insert(parsed_flag),
+                Err(err) => report(err)?,
+            }
+        }
+
+        match rest {
+            Some(rest) => flags = rest,
+            None => break,
+        }
+    }
+
+    Ok(fix_reserved(parsed_flags))
+}
+
+// Split the first flag from text that starts with a flag, and may contain more flags
+// separated by whitespace
+//
+// If expressions are enabled then whitespace around `-` and after `!` doesn't separate flags,
+// so `all - A` and `! A` are single flags
+fn split_whitespace<'a, T: Text + ?Sized>(
+    text: &'a T,
+    options: &ParserOptions<'_>,
+) -> (&'a T, &'a T) {
+    let len = text.as_ref().len();
+    let word_end = |start: usize| {
+        text.slice(start..len)
+            .find_whitespace()
+            .map_or(len, |end| start + end)
+    };
+
+    let mut end = word_end(0);
+    if options.expressions {
+        loop {
+            let next = len - text.slice(end..len).trim_start().as_ref().len();
+
+            let operator = matches!(text.as_ref()[..end].last(), Some(b'-' | b'!'))
+                || text.as_ref()[next..].first() == Some(&b'-');
+
+            if next < len && operator {
+                end = word_end(next);
+            } else {
+                break;
+            }
+        }
+    }
+
+    (text.slice(0..end), text.slice(end..len).trim_start())
+}
+
+// Parse a single non-empty flag, like `A`, `MODE=Fast`, or `0x1`
+//
+// If expressions are enabled, the flag may also be an expression, like `all - A`
+fn parse_flag<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    flag: &T,
+    options: &ParserOptions<'_>,
+    parse_number: &impl Fn(&T, &T, Radix, &T) -> Result<B, ParseError>,
+) -> Result<B, ParseError> {
+    if options.expressions {
+        let mut rest = flag;
+        let mut parsed_flags = None;
+
+        // There's always at least one operand, even if it's empty
+        loop {
+            let (operand, remaining) = split_operand::<B, T>(rest, options);
+
+            let parsed_flag = parse_operand::<B, T>(input, operand, options, parse_number)?;
+            parsed_flags = Some(match parsed_flags {
+                Some(parsed_flags) => B::difference(parsed_flags, parsed_flag),
+                None => parsed_flag,
+            });
+
+            match remaining {
+                Some(remaining) => rest = remaining,
+                None => break,
+            }
         }
 
-        // If the flag starts with `0x` then it's a hex number
-        // Parse it directly to the underlying bits type
-        let parsed_flag = if let Some(flag) = flag.strip_prefix("0x") {
-            let bits =
-                <B::Bits>::parse_hex(flag).map_err(|_| ParseError::invalid_hex_flag(flag))?;
+        Ok(parsed_flags.unwrap_or_else(B::empty))
+    } else {
+        parse_term(input, flag, options, parse_number)
+    }
+}
 
-            B::from_bits_retain(bits)
+// Split the first operand from an expression at a `-`
+//
+// Names of flags may contain `-`, like `read-only`, so the longest operand that's a defined
+// name is preferred. Otherwise the operand ends at the first `-`
+fn split_operand<'a, B: Flags, T: Text + ?Sized>(
+    expression: &'a T,
+    options: &ParserOptions<'_>,
+) -> (&'a T, Option<&'a T>) {
+    let is_name = |operand: &T| {
+        let mut operand = operand.trim();
+        while let Some(complemented) = operand.strip_prefix("!") {
+            operand = complemented.trim();
         }
-        // Otherwise the flag is a name
-        // The generated flags type will determine whether
-        // or not it's a valid identifier
-        else {
-            B::from_name(flag).ok_or_else(|| ParseError::invalid_named_flag(flag))?
+
+        parse_name::<B, T>(operand, options).is_some()
+    };
+
+    if is_name(expression) {
+        return (expression, None);
+    }
+
+    let len = expression.as_ref().len();
+    if let Some(end) = (0..len)
+        .rev()
+        .filter(|&end| expression.as_ref()[end] == b'-')
+        .find(|&end| is_name(expression.slice(0..end)))
+    {
+        return (
+            expression.slice(0..end),
+            Some(expression.slice(end + 1..len)),
+        );
+    }
+
+    match expression.split_once("-") {
+        Some((operand, rest)) => (operand, Some(rest)),
+        None => (expression, None),
+    }
+}
+
+// Parse an operand in an expression, like `!A` or `all`
+fn parse_operand<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    operand: &T,
+    options: &ParserOptions<'_>,
+    parse_number: &impl Fn(&T, &T, Radix, &T) -> Result<B, ParseError>,
+) -> Result<B, ParseError> {
+    let operand = operand.trim();
+
+    if operand.is_empty() {
+        return Err(ParseError::empty_flag().with_span(span(input, operand)));
+    }
+
+    if let Some(operand) = operand.strip_prefix("!") {
+        return Ok(parse_operand::<B, T>(input, operand, options, parse_number)?.complement());
+    }
+
+    // Defined flags take precedence over keywords
+    if let Some(parsed_flag) = parse_name(operand, options) {
+        return Ok(parsed_flag);
+    }
+
+    let operand_bytes = operand.as_ref();
+    if options.name_eq("all", operand_bytes) {
+        Ok(B::all())
+    } else if options.name_eq("empty", operand_bytes) || options.name_eq("none", operand_bytes) {
+        Ok(B::empty())
+    } else {
+        parse_term(input, operand, options, parse_number)
+    }
+}
+
+// Parse a name, field, or number
+fn parse_term<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    flag: &T,
+    options: &ParserOptions<'_>,
+    parse_number: &impl Fn(&T, &T, Radix, &T) -> Result<B, ParseError>,
+) -> Result<B, ParseError> {
+    // If the flag is qualified by the name of the type then parse it as a Rust expression
+    if let Some(flag) = strip_type_path(flag, options) {
+        return parse_qualified(input, flag, options, parse_number);
+    }
+
+    // If the flag starts with a digit then it's a number
+    // Parse it directly to the underlying bits type
+    if let Some((radix, digits)) = split_radix(flag, options) {
+        parse_number(input, flag, radix, digits)
+    }
+    // If the flag contains `=` then it's a field
+    else if let Some((name, variant)) = flag.split_once("=") {
+        parse_field(name, variant, options).ok_or_else(|| {
+            invalid_named_flag::<B, T>(input, flag, name.trim(), |flag| flag.is_field())
+        })
+    }
+    // Otherwise the flag is a name
+    // The generated flags type will determine whether
+    // or not it's a valid identifier
+    else {
+        parse_name(flag, options)
+            .ok_or_else(|| invalid_named_flag::<B, T>(input, flag, flag, |flag| flag.is_named()))
+    }
+}
+
+// Parse a flag that was qualified by the name of the type, like `A`, `empty()`, or `from_bits_retain(0x1)`
+fn parse_qualified<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    flag: &T,
+    options: &ParserOptions<'_>,
+    parse_number: &impl Fn(&T, &T, Radix, &T) -> Result<B, ParseError>,
+) -> Result<B, ParseError> {
+    if let Some(bits) = flag
+        .strip_prefix("from_bits_retain(")
+        .and_then(|bits| bits.strip_suffix(")"))
+    {
+        let bits = bits.trim();
+
+        return if split_radix(bits, options).is_some() {
+            parse_term(input, bits, options, parse_number)
+        } else {
+            Err(bits
+                .error(ParseErrorKind::InvalidNumberFlag)
+                .with_span(span(input, bits)))
         };
+    }
+
+    if flag.as_ref() == b"empty()" {
+        return Ok(B::empty());
+    }
+
+    parse_term(input, flag, options, parse_number)
+}
 
-        parsed_flags.insert(parsed_flag);
+// Strip the name of the type wrapping the input, like `MyFlags(A | B)`
+fn strip_type_wrapper<'a, T: Text + ?Sized>(
+    input: &'a T,
+    options: &ParserOptions<'_>,
+) -> Option<&'a T> {
+    let type_name = options.type_name?;
+    let input = input.trim();
+
+    let start = input.as_ref().get(..type_name.len())?;
+    if !options.name_eq(type_name, start) {
+        return None;
     }
 
-    Ok(parsed_flags)
+    let flags = input
+        .slice(type_name.len()..input.as_ref().len())
+        .trim_start()
+        .strip_prefix("(")?
+        .strip_suffix(")")?
+        .trim_end();
+
+    // Pretty `Debug` output includes a trailing comma
+    Some(flags.strip_suffix(",").unwrap_or(flags))
+}
+
+// Strip the path to the type from a qualified flag, like `MyFlags::A` or `crate::MyFlags::A`
+fn strip_type_path<'a, T: Text + ?Sized>(
+    flag: &'a T,
+    options: &ParserOptions<'_>,
+) -> Option<&'a T> {
+    let type_name = options.type_name?;
+    let (path, flag) = flag.rsplit_once("::")?;
+
+    // The type may be qualified by a module path, like `crate::MyFlags`
+    let ty = path.rsplit_once("::").map_or(path, |(_, ty)| ty);
+
+    if options.name_eq(type_name, ty.trim().as_ref()) {
+        Some(flag.trim())
+    } else {
+        None
+    }
+}
+
+// An unrecognized named flag, with a suggestion for the defined flag nearest to `name`
+fn invalid_named_flag<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    flag: &T,
+    name: &T,
+    candidate: impl Fn(&Flag<B>) -> bool,
+) -> ParseError {
+    let error = flag
+        .error(ParseErrorKind::InvalidNamedFlag)
+        .with_span(span(input, flag));
+
+    match name.suggest(B::FLAGS.iter().filter(|flag| candidate(flag))) {
+        Some(suggestion) => error.with_suggestion(suggestion),
+        None => error,
+    }
+}
+
+// Parse the digits of a number to the bits type
+fn parse_number<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    flag: &T,
+    radix: Radix,
+    digits: &T,
+) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    let bits = digits.parse_radix::<B::Bits>(radix).map_err(|_| {
+        if radix == Radix::Hex {
+            digits.error(ParseErrorKind::InvalidHexFlag)
+        } else {
+            flag.error(ParseErrorKind::InvalidNumberFlag)
+        }
+        .with_span(span(input, flag))
+    })?;
+
+    Ok(B::from_bits_retain(bits))
+}
+
+// Reject a number, because they aren't supported by the strict parser
+fn reject_number<B: Flags, T: Text + ?Sized>(
+    input: &T,
+    flag: &T,
+    radix: Radix,
+    _: &T,
+) -> Result<B, ParseError> {
+    Err(if radix == Radix::Hex {
+        ParseError::invalid_hex_flag("unsupported hex flag value")
+    } else {
+        ParseError::invalid_number_flag("unsupported number flag value")
+    }
+    .with_span(span(input, flag)))
 }
 
 /**
@@ -167,14 +704,16 @@ pub fn to_writer_strict<B: Flags>(flags: &B, mut writer: impl Write) -> Result<(
     // any bits not corresponding to a named flag
 
     let mut first = true;
+    let (flags, _) = write_fields(flags, &mut first, &mut writer, &ParserOptions::new())?;
+
     let mut iter = flags.iter_names();
-    for (name, _) in &mut iter {
+    while let Some((flag, _)) = iter.next_flag() {
         if !first {
             writer.write_str(" | ")?;
         }
 
         first = false;
-        writer.write_str(name)?;
+        writer.write_str(flag.display_name())?;
     }
 
     fmt::Result::Ok(())
@@ -187,146 +726,1392 @@ This function will fail on any names that don't correspond to defined flags.
 This function will fail to parse hex values.
 */
 pub fn from_str_strict<B: Flags>(input: &str) -> Result<B, ParseError> {
-    // This is a simplified version of `from_str` that ignores
-    // any bits not corresponding to a named flag
-
-    let mut parsed_flags = B::empty();
-
-    // If the input is empty then return an empty set of flags
-    if input.trim().is_empty() {
-        return Ok(parsed_flags);
-    }
-
-    for flag in input.split('|') {
-        let flag = flag.trim();
-
-        // If the flag is empty then we've got missing input
-        if flag.is_empty() {
-            return Err(ParseError::empty_flag());
-        }
+    // This is a simplified version of `from_str` that doesn't support numbers
+    parse(input, &ParserOptions::new(), &reject_number, Err)
+}
 
-        // If the flag starts with `0x` then it's a hex number
-        // These aren't supported in the strict parser
-        if flag.starts_with("0x") {
-            return Err(ParseError::invalid_hex_flag("unsupported hex flag value"));
-        }
+/**
+Parse a flags value from a sequence of flags, like `["A", "B", "0x0c"]`.
 
-        let parsed_flag = B::from_name(flag).ok_or_else(|| ParseError::invalid_named_flag(flag))?;
+Each item is a single _Flag_ from the text grammar, so it may be a name, a field, or a number.
+Errors are relative to the item that caused them, and carry its [index](ParseError::index).
+This function will fail on any names that don't correspond to defined flags.
+Unknown bits will be retained.
+*/
+pub fn from_names<B: Flags, I>(names: I) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut parsed_flags = B::empty();
 
-        parsed_flags.insert(parsed_flag);
+    for (index, name) in names.into_iter().enumerate() {
+        parsed_flags.insert(parse_item(name.as_ref(), index, false)?);
     }
 
-    Ok(parsed_flags)
+    Ok(fix_reserved(parsed_flags))
 }
 
 /**
-Encode a value as a hex string.
+Parse a flags value from a sequence of flags.
 
-Implementors of this trait should not write the `0x` prefix.
+This function will fail on any names that don't correspond to defined flags.
+Unknown bits will be ignored.
 */
-pub trait WriteHex {
-    /// Write the value as hex.
-    fn write_hex<W: fmt::Write>(&self, writer: W) -> fmt::Result;
+pub fn from_names_truncate<B: Flags, I>(names: I) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Ok(B::from_bits_truncate(from_names::<B, I>(names)?.bits()))
 }
 
 /**
-Parse a value from a hex string.
+Parse a flags value from a sequence of flags.
+
+This function will fail on any names that don't correspond to defined flags.
+This function will fail to parse numbers.
 */
-pub trait ParseHex {
-    /// Parse the value from hex.
-    fn parse_hex(input: &str) -> Result<Self, ParseError>
-    where
-        Self: Sized;
-}
+pub fn from_names_strict<B: Flags, I>(names: I) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut parsed_flags = B::empty();
 
-/// An error encountered while parsing flags from text.
-#[derive(Debug)]
-pub struct ParseError(ParseErrorKind);
+    for (index, name) in names.into_iter().enumerate() {
+        parsed_flags.insert(parse_item(name.as_ref(), index, true)?);
+    }
 
-#[derive(Debug)]
-#[allow(clippy::enum_variant_names)]
-enum ParseErrorKind {
-    EmptyFlag,
-    InvalidNamedFlag {
-        #[cfg(not(feature = "std"))]
-        got: (),
-        #[cfg(feature = "std")]
-        got: String,
-    },
-    InvalidHexFlag {
-        #[cfg(not(feature = "std"))]
-        got: (),
-        #[cfg(feature = "std")]
-        got: String,
-    },
+    Ok(fix_reserved(parsed_flags))
 }
 
-impl ParseError {
-    /// An invalid hex flag was encountered.
-    pub fn invalid_hex_flag(flag: impl fmt::Display) -> Self {
-        let _flag = flag;
+// Parse a single flag from a sequence of flags
+//
+// If `strict` is set then numbers aren't supported
+pub(crate) fn parse_item<B: Flags>(item: &str, index: usize, strict: bool) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    let options = ParserOptions::new();
+    let flag = item.trim();
+
+    let parsed_flag = if flag.is_empty() {
+        Err(ParseError::empty_flag().with_span(span(item, flag)))
+    } else if strict && split_radix(flag, &options).is_some() {
+        Err(
+            ParseError::invalid_number_flag("unsupported number flag value")
+                .with_span(span(item, flag)),
+        )
+    } else {
+        parse_term(item, flag, &options, &parse_number)
+    };
+
+    parsed_flag.map_err(|err| err.with_index(index))
+}
 
-        let got = {
-            #[cfg(feature = "std")]
-            {
-                _flag.to_string()
-            }
-        };
+// A single flag in a sequence of flags, like `A`, `MODE=Fast`, or `0x1`
+#[cfg(feature = "serde")]
+pub(crate) enum Item<B: Flags> {
+    Name(&'static str),
+    Field(&'static str, &'static str),
+    Bits(B::Bits),
+}
 
-        ParseError(ParseErrorKind::InvalidHexFlag { got })
+#[cfg(feature = "serde")]
+impl<B: Flags> fmt::Display for Item<B>
+where
+    B::Bits: WriteHex,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Item::Name(name) => f.write_str(name),
+            Item::Field(name, variant) => write!(f, "{}={}", name, variant),
+            Item::Bits(bits) => bits.write_radix(f, Radix::Hex),
+        }
     }
+}
 
-    /// A named flag that doesn't correspond to any on the flags type was encountered.
-    pub fn invalid_named_flag(flag: impl fmt::Display) -> Self {
-        let _flag = flag;
+// Visit each defined flag in a flags value, in the order they're formatted as text
+//
+// Any remaining bits that don't correspond to a flag are returned instead of visited
+#[cfg(feature = "serde")]
+pub(crate) fn for_each_item<B: Flags, E>(
+    flags: &B,
+    mut visit: impl FnMut(Item<B>) -> Result<(), E>,
+) -> Result<B::Bits, E> {
+    let mut remaining = flags.bits();
+    let mut unknown = B::Bits::EMPTY;
+
+    // Visit any fields first, like `write_fields`
+    for flag in B::FLAGS.iter().filter(|flag| flag.is_field()) {
+        let mask = flag.value().bits();
+        remaining = remaining & !mask;
+
+        if flags.bits() & mask == B::Bits::EMPTY {
+            continue;
+        }
 
-        let got = {
-            #[cfg(feature = "std")]
-            {
-                _flag.to_string()
-            }
+        match flag.field_variant(flags) {
+            Some(variant) => visit(Item::Field(flag.display_name(), variant))?,
+            None => unknown = unknown | (flags.bits() & mask),
+        }
+    }
+
+    let mut iter = B::from_bits_retain(remaining).iter_names();
+    while let Some((flag, _)) = iter.next_flag() {
+        visit(Item::Name(flag.display_name()))?;
+    }
+
+    Ok((iter.remaining().bits() | unknown) & !reserved::<B>())
+}
+
+// Find the defined flag with the given name, display name, or alias
+#[cfg(feature = "serde")]
+pub(crate) fn find_flag<B: Flags>(name: &str) -> Result<&'static Flag<B>, ParseError> {
+    let options = ParserOptions::new();
+
+    B::FLAGS
+        .iter()
+        .find(|flag| flag.is_named() && options.has_name(flag, name.as_bytes()))
+        .ok_or_else(|| invalid_named_flag::<B, str>(name, name, name, |flag| flag.is_named()))
+}
+
+/**
+Parse a flags value from ASCII text in bytes.
+
+This function is like [`from_str`], but doesn't need the input to be valid UTF-8.
+It will fail on any names that don't correspond to defined flags.
+Unknown bits will be retained.
+*/
+pub fn from_bytes<B: Flags>(input: &[u8]) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    from_bytes_with(input, &ParserOptions::new())
+}
+
+/**
+Parse a flags value from ASCII text in bytes, using the grammar from the given options.
+
+This function is like [`from_str_with`], but doesn't need the input to be valid UTF-8.
+Only ASCII whitespace is trimmed from the input.
+It will fail on any names that don't correspond to defined flags.
+Unknown bits will be retained.
+*/
+pub fn from_bytes_with<B: Flags>(input: &[u8], options: &ParserOptions<'_>) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    // Stop at the first error
+    parse(input, options, &parse_number, Err)
+}
+
+/**
+Parse a flags value from ASCII text in bytes.
+
+This function is like [`from_str_truncate`], but doesn't need the input to be valid UTF-8.
+It will fail on any names that don't correspond to defined flags.
+Unknown bits will be ignored.
+*/
+pub fn from_bytes_truncate<B: Flags>(input: &[u8]) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    Ok(B::from_bits_truncate(from_bytes::<B>(input)?.bits()))
+}
+
+/**
+Parse a flags value from ASCII text in bytes.
+
+This function is like [`from_str_strict`], but doesn't need the input to be valid UTF-8.
+It will fail on any names that don't correspond to defined flags.
+It will fail to parse hex values.
+*/
+pub fn from_bytes_strict<B: Flags>(input: &[u8]) -> Result<B, ParseError> {
+    parse(input, &ParserOptions::new(), &reject_number, Err)
+}
+
+// Text that flags can be parsed from, either a `str` or ASCII text in bytes
+//
+// The grammar is ASCII, so text is only ever split before or after ASCII bytes, or around
+// the UTF-8 encoding of a separator. These are always `char` boundaries in a `str`
+trait Text: AsRef<[u8]> {
+    // The text in a range of bytes
+    fn slice(&self, range: Range<usize>) -> &Self;
+
+    // Remove whitespace from the start or end of the text
+    fn trim_start(&self) -> &Self;
+    fn trim_end(&self) -> &Self;
+
+    // The byte index of the first whitespace in the text
+    fn find_whitespace(&self) -> Option<usize>;
+
+    // An error of the given kind, caused by this text
+    fn error(&self, kind: ParseErrorKind) -> ParseError;
+
+    // Parse a named flag by the name it's declared with, using case-sensitive matching
+    fn parse_declared_name<B: Flags>(&self) -> Option<B>;
+
+    // Parse the variant of a field
+    fn parse_field_variant<B>(&self, flag: &Flag<B>, case_sensitive: bool) -> Option<B>;
+
+    // Parse the digits of a number, without its prefix
+    fn parse_radix<U: ParseHex>(&self, radix: Radix) -> Result<U, ParseError>;
+
+    // Find the name of the flag nearest to this text, if any are near enough to be a likely typo
+    fn suggest<'a, B: 'a>(&self, flags: impl Iterator<Item = &'a Flag<B>>) -> Option<&'static str>;
+
+    fn trim(&self) -> &Self {
+        self.trim_end().trim_start()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.as_ref().is_empty()
+    }
+
+    fn strip_prefix(&self, prefix: &str) -> Option<&Self> {
+        let bytes = self.as_ref();
+
+        if bytes.starts_with(prefix.as_bytes()) {
+            Some(self.slice(prefix.len()..bytes.len()))
+        } else {
+            None
+        }
+    }
+
+    fn strip_suffix(&self, suffix: &str) -> Option<&Self> {
+        let bytes = self.as_ref();
+
+        if bytes.ends_with(suffix.as_bytes()) {
+            Some(self.slice(0..bytes.len() - suffix.len()))
+        } else {
+            None
+        }
+    }
+
+    // Split the text around the first occurrence of `pat`
+    fn split_once(&self, pat: &str) -> Option<(&Self, &Self)> {
+        let bytes = self.as_ref();
+        let start = (0..bytes.len()).find(|&i| bytes[i..].starts_with(pat.as_bytes()))?;
+
+        Some((
+            self.slice(0..start),
+            self.slice(start + pat.len()..bytes.len()),
+        ))
+    }
+
+    // Split the text around the last occurrence of `pat`
+    fn rsplit_once(&self, pat: &str) -> Option<(&Self, &Self)> {
+        let bytes = self.as_ref();
+        let start = (0..bytes.len())
+            .rev()
+            .find(|&i| bytes[i..].starts_with(pat.as_bytes()))?;
+
+        Some((
+            self.slice(0..start),
+            self.slice(start + pat.len()..bytes.len()),
+        ))
+    }
+
+    // Split the text around the first occurrence of any of `chars`
+    fn split_once_any(&self, chars: &[char]) -> Option<(&Self, &Self)> {
+        let bytes = self.as_ref();
+
+        for i in 0..bytes.len() {
+            for c in chars {
+                let mut buf = [0; 4];
+                let c = c.encode_utf8(&mut buf).as_bytes();
+
+                if bytes[i..].starts_with(c) {
+                    return Some((self.slice(0..i), self.slice(i + c.len()..bytes.len())));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Text for str {
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+
+    fn trim_start(&self) -> &Self {
+        str::trim_start(self)
+    }
+
+    fn trim_end(&self) -> &Self {
+        str::trim_end(self)
+    }
+
+    fn find_whitespace(&self) -> Option<usize> {
+        self.find(char::is_whitespace)
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, self)
+    }
+
+    fn parse_declared_name<B: Flags>(&self) -> Option<B> {
+        B::from_name(self)
+    }
+
+    fn parse_field_variant<B>(&self, flag: &Flag<B>, case_sensitive: bool) -> Option<B> {
+        if case_sensitive {
+            flag.parse_field_variant(self)
+        } else {
+            flag.parse_field_variant_ignore_case(self)
+        }
+    }
+
+    fn parse_radix<U: ParseHex>(&self, radix: Radix) -> Result<U, ParseError> {
+        U::parse_radix(self, radix)
+    }
+
+    fn suggest<'a, B: 'a>(&self, flags: impl Iterator<Item = &'a Flag<B>>) -> Option<&'static str> {
+        suggest(self.chars(), flags)
+    }
+}
+
+impl Text for [u8] {
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+
+    fn trim_start(&self) -> &Self {
+        let start = self
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(self.len());
+
+        &self[start..]
+    }
+
+    fn trim_end(&self) -> &Self {
+        let end = self
+            .iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(0, |end| end + 1);
+
+        &self[..end]
+    }
+
+    fn find_whitespace(&self) -> Option<usize> {
+        self.iter().position(u8::is_ascii_whitespace)
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, AsciiDisplay(self))
+    }
+
+    fn parse_declared_name<B: Flags>(&self) -> Option<B> {
+        B::FLAGS
+            .iter()
+            .find(|flag| flag.is_named() && flag.name().as_bytes() == self)
+            .map(|flag| B::from_bits_retain(flag.value().bits()))
+    }
+
+    fn parse_field_variant<B>(&self, flag: &Flag<B>, case_sensitive: bool) -> Option<B> {
+        // Only the variant needs to be valid UTF-8
+        core::str::from_utf8(self)
+            .ok()
+            .and_then(|variant| variant.parse_field_variant(flag, case_sensitive))
+    }
+
+    fn parse_radix<U: ParseHex>(&self, radix: Radix) -> Result<U, ParseError> {
+        U::parse_radix_bytes(self, radix)
+    }
+
+    fn suggest<'a, B: 'a>(&self, flags: impl Iterator<Item = &'a Flag<B>>) -> Option<&'static str> {
+        suggest(self.iter().map(|b| char::from(*b)), flags)
+    }
+}
+
+// Display bytes that are expected to be ASCII text, escaping any bytes that aren't
+pub(crate) struct AsciiDisplay<'a>(pub(crate) &'a [u8]);
+
+impl<'a> fmt::Display for AsciiDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            if b.is_ascii_graphic() || *b == b' ' {
+                f.write_char(char::from(*b))?;
+            } else {
+                write!(f, "\\x{:02x}", b)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/**
+Write a flags value as a Rust expression.
+
+Each contained flag is written as a path to its constant, like `MyFlags::A`, with `path` as
+the path to the flags type. Any bits that aren't part of a contained flag are written as a call
+to `from_bits_retain`. An empty flags value is written as a call to `empty`.
+*/
+pub fn to_writer_rust<B: Flags>(
+    flags: &B,
+    mut writer: impl Write,
+    path: &str,
+) -> Result<(), fmt::Error>
+where
+    B::Bits: WriteHex,
+{
+    let written = write_expr(
+        flags,
+        &mut writer,
+        |writer, name| write!(writer, "{}::{}", path, name),
+        |writer, bits| {
+            write!(writer, "{}::from_bits_retain(0x", path)?;
+            bits.write_hex(&mut *writer)?;
+            writer.write_str(")")
+        },
+    )?;
+
+    if !written {
+        write!(writer, "{}::empty()", path)?;
+    }
+
+    fmt::Result::Ok(())
+}
+
+/**
+Write a flags value as a C expression.
+
+Each contained flag is written as its name with the given `prefix`, like `FLAG_A`. Any bits that
+aren't part of a contained flag are written as a hex number. An empty flags value is written
+as `0`.
+*/
+pub fn to_writer_c<B: Flags>(
+    flags: &B,
+    mut writer: impl Write,
+    prefix: &str,
+) -> Result<(), fmt::Error>
+where
+    B::Bits: WriteHex,
+{
+    let written = write_expr(
+        flags,
+        &mut writer,
+        |writer, name| write!(writer, "{}{}", prefix, name),
+        |writer, bits| {
+            writer.write_str("0x")?;
+            bits.write_hex(writer)
+        },
+    )?;
+
+    if !written {
+        writer.write_str("0")?;
+    }
+
+    fmt::Result::Ok(())
+}
+
+// Write a flags value as the names of its contained flags and any remaining bits, separated by ` | `
+//
+// This is the same as `to_writer`, but without any fields. The returned value is whether
+// anything was written
+fn write_expr<B: Flags, W: Write>(
+    flags: &B,
+    writer: &mut W,
+    mut write_name: impl FnMut(&mut W, &str) -> fmt::Result,
+    write_bits: impl FnOnce(&mut W, B::Bits) -> fmt::Result,
+) -> Result<bool, fmt::Error> {
+    let mut first = true;
+
+    let mut iter = flags.iter_names();
+    for (name, _) in &mut iter {
+        if !first {
+            writer.write_str(" | ")?;
+        }
+
+        first = false;
+        write_name(writer, name)?;
+    }
+
+    // Reserved bits are never formatted
+    let remaining = iter.remaining().bits() & !reserved::<B>();
+    if remaining != B::Bits::EMPTY {
+        if !first {
+            writer.write_str(" | ")?;
+        }
+
+        first = false;
+        write_bits(writer, remaining)?;
+    }
+
+    Ok(!first)
+}
+
+// Write any fields with bits set as `NAME=Variant`
+//
+// The returned flags value has the bits of all fields unset, along with the bits of
+// any fields that didn't correspond to a variant
+fn write_fields<B: Flags>(
+    flags: &B,
+    first: &mut bool,
+    mut writer: impl Write,
+    options: &ParserOptions<'_>,
+) -> Result<(B, B::Bits), fmt::Error> {
+    let mut remaining = flags.bits();
+    let mut unknown = B::Bits::EMPTY;
+
+    for flag in B::FLAGS.iter().filter(|flag| flag.is_field()) {
+        let mask = flag.value().bits();
+        remaining = remaining & !mask;
+
+        // Fields with no bits set aren't written, so empty flags values are formatted as empty text
+        if flags.bits() & mask == B::Bits::EMPTY {
+            continue;
+        }
+
+        if let Some(variant) = flag.field_variant(flags) {
+            if !*first {
+                writer.write_str(options.write_separator)?;
+            }
+
+            *first = false;
+            writer.write_str(flag.display_name())?;
+            writer.write_str("=")?;
+            writer.write_str(variant)?;
+        } else {
+            unknown = unknown | (flags.bits() & mask);
+        }
+    }
+
+    Ok((B::from_bits_retain(remaining), unknown))
+}
+
+// Finding the smallest set of names is exponential in the number of candidates,
+// so values with more candidates than this use an approximation
+//
+// The search prunes any set that isn't smaller than the best one found so far, so it's much
+// faster than trying every set in practice. The slowest values are ones where every candidate
+// overlaps others, like chains of flags that each share a bit with the next. With 16 candidates
+// those still format in around 10µs in optimized builds, but the time roughly doubles with every
+// few more candidates, so this limit keeps formatting fast while still covering most values
+const MAX_EXACT_CANONICAL_CANDIDATES: usize = 16;
+
+// Write the smallest set of names that covers the bits of a flags value
+//
+// Names are written in the order they're declared in. The returned bits are any that
+// aren't covered by a name
+fn write_canonical<B: Flags>(
+    flags: &B,
+    first: &mut bool,
+    mut writer: impl Write,
+    options: &ParserOptions<'_>,
+) -> Result<B::Bits, fmt::Error> {
+    let value = flags.bits();
+
+    let covered = canonical_candidates::<B>(value)
+        .fold(B::Bits::EMPTY, |covered, candidate| covered | candidate.1);
+
+    if canonical_candidates::<B>(value)
+        .nth(MAX_EXACT_CANONICAL_CANDIDATES)
+        .is_some()
+    {
+        write_canonical_approx::<B>(value, first, writer, options)?;
+
+        return Ok(value & !covered);
+    }
+
+    let mut uncovered = covered;
+    let mut len = cover_len::<B>(value, uncovered, usize::MAX).unwrap_or(0);
+
+    // Take each candidate in order as long as there's still a smallest cover that includes it
+    // Since each choice only narrows the smallest covers, a skipped candidate can't be
+    // needed by a later one
+    for (name, bits) in canonical_candidates::<B>(value) {
+        if len == 0 {
+            break;
+        }
+
+        if bits & uncovered == B::Bits::EMPTY
+            || cover_len::<B>(value, uncovered & !bits, len).is_none()
+        {
+            continue;
+        }
+
+        if !*first {
+            writer.write_str(options.write_separator)?;
+        }
+
+        *first = false;
+        writer.write_str(name)?;
+
+        uncovered = uncovered & !bits;
+        len -= 1;
+    }
+
+    Ok(value & !covered)
+}
+
+// Write a small set of names that covers the bits of a flags value
+//
+// Names are picked greedily from the candidates that aren't contained in any other, which
+// still cover all bits, and then any picked name whose bits are covered by the others is
+// skipped. The result may not be the smallest set, but no name in it is redundant
+fn write_canonical_approx<B: Flags>(
+    value: B::Bits,
+    first: &mut bool,
+    mut writer: impl Write,
+    options: &ParserOptions<'_>,
+) -> fmt::Result {
+    let mut written = B::Bits::EMPTY;
+    for (i, (name, bits)) in canonical_greedy::<B>(value).enumerate() {
+        // Picked names are only skipped if the names already written and the ones
+        // picked after them cover their bits, so the written names always cover all bits
+        let later = canonical_greedy::<B>(value)
+            .skip(i + 1)
+            .fold(B::Bits::EMPTY, |later, candidate| later | candidate.1);
+
+        if bits & !(written | later) == B::Bits::EMPTY {
+            continue;
+        }
+
+        if !*first {
+            writer.write_str(options.write_separator)?;
+        }
+
+        *first = false;
+        writer.write_str(name)?;
+
+        written = written | bits;
+    }
+
+    Ok(())
+}
+
+// The candidates picked by a greedy cover of the bits of a flags value
+//
+// Each candidate that isn't contained in any other is picked, in order, if it covers any bits
+// that earlier picks didn't
+fn canonical_greedy<B: Flags>(value: B::Bits) -> impl Iterator<Item = (&'static str, B::Bits)> {
+    let is_maximal = move |bits: B::Bits| {
+        !canonical_candidates::<B>(value)
+            .any(|other| other.1 != bits && bits & !other.1 == B::Bits::EMPTY)
+    };
+
+    let mut uncovered = value;
+    canonical_candidates::<B>(value).filter(move |&(_, bits)| {
+        if bits & uncovered == B::Bits::EMPTY || !is_maximal(bits) {
+            return false;
+        }
+
+        uncovered = uncovered & !bits;
+        true
+    })
+}
+
+// The named flags that could be used to cover the bits of a flags value
+//
+// These are any non-empty named flags that are fully contained in the value
+fn canonical_candidates<B: Flags>(value: B::Bits) -> impl Iterator<Item = (&'static str, B::Bits)> {
+    B::FLAGS.iter().filter_map(move |flag| {
+        let bits = flag.value().bits();
+
+        if flag.is_named()
+            && !flag.is_field()
+            && bits != B::Bits::EMPTY
+            && bits & !value == B::Bits::EMPTY
+        {
+            Some((flag.display_name(), bits))
+        } else {
+            None
+        }
+    })
+}
+
+// The fewest candidates needed to cover `uncovered`, if that's fewer than `limit`
+fn cover_len<B: Flags>(value: B::Bits, uncovered: B::Bits, limit: usize) -> Option<usize> {
+    if uncovered == B::Bits::EMPTY {
+        return Some(0);
+    }
+
+    // At least one more candidate is needed, which would reach the limit
+    if limit <= 1 {
+        return None;
+    }
+
+    // Any cover must include a candidate that overlaps the uncovered bits of the first
+    // overlapping candidate, so only those need to be tried
+    let (_, first) =
+        canonical_candidates::<B>(value).find(|c| c.1 & uncovered != B::Bits::EMPTY)?;
+    let pivot = first & uncovered;
+
+    let mut limit = limit;
+    let mut len = None;
+    for (_, bits) in canonical_candidates::<B>(value).filter(|c| c.1 & pivot != B::Bits::EMPTY) {
+        if let Some(rest) = cover_len::<B>(value, uncovered & !bits, limit - 1) {
+            len = Some(rest + 1);
+            limit = rest + 1;
+        }
+    }
+
+    len
+}
+
+// Parse a field from `NAME=Variant`
+fn parse_field<B: Flags, T: Text + ?Sized>(
+    name: &T,
+    variant: &T,
+    options: &ParserOptions<'_>,
+) -> Option<B> {
+    let (name, variant) = (name.trim(), variant.trim());
+
+    B::FLAGS
+        .iter()
+        .find(|flag| flag.is_field() && options.has_name(flag, name.as_ref()))
+        .and_then(|flag| variant.parse_field_variant(flag, options.case_sensitive))
+}
+
+// Split a number into its radix and digits
+fn split_radix<'a, T: Text + ?Sized>(
+    flag: &'a T,
+    options: &ParserOptions<'_>,
+) -> Option<(Radix, &'a T)> {
+    let bytes = flag.as_ref();
+
+    for radix in [Radix::Hex, Radix::Binary, Radix::Octal] {
+        let prefix = radix.prefix();
+
+        if let Some(start) = bytes.get(..prefix.len()) {
+            if options.name_eq(prefix, start) {
+                return Some((radix, flag.slice(prefix.len()..bytes.len())));
+            }
+        }
+    }
+
+    if bytes.first().map_or(false, u8::is_ascii_digit) {
+        Some((Radix::Decimal, flag))
+    } else {
+        None
+    }
+}
+
+// Parse a named flag
+fn parse_name<B: Flags, T: Text + ?Sized>(name: &T, options: &ParserOptions<'_>) -> Option<B> {
+    if options.case_sensitive {
+        if let Some(flag) = name.parse_declared_name() {
+            return Some(flag);
+        }
+    }
+
+    B::FLAGS
+        .iter()
+        .find(|flag| flag.is_named() && options.has_name(flag, name.as_ref()))
+        .map(|flag| B::from_bits_retain(flag.value().bits()))
+}
+
+// Whether a flag has the given name, display name, or alias
+fn has_name<B>(
+    flag: &Flag<B>,
+    deprecated_aliases: bool,
+    mut name_eq: impl FnMut(&'static str) -> bool,
+) -> bool {
+    name_eq(flag.name())
+        || name_eq(flag.display_name())
+        || flag.aliases().iter().any(|alias| name_eq(alias))
+        || (deprecated_aliases && flag.deprecated_aliases().iter().any(|alias| name_eq(alias)))
+}
+
+// The byte range of `part` within `input`
+//
+// `part` must be a subslice of `input`
+fn span<T: AsRef<[u8]> + ?Sized>(input: &T, part: &T) -> Range<usize> {
+    let (input, part) = (input.as_ref(), part.as_ref());
+    let start = part.as_ptr() as usize - input.as_ptr() as usize;
+
+    start..start + part.len()
+}
+
+// Find the name of the flag nearest to `name`, if any are near enough to be a likely typo
+fn suggest<'a, B: 'a>(
+    name: impl Iterator<Item = char> + Clone,
+    flags: impl Iterator<Item = &'a Flag<B>>,
+) -> Option<&'static str> {
+    // Allow roughly one edit for every three characters
+    let max_distance = core::cmp::max(1, name.clone().count() / 3);
+
+    let mut nearest = None;
+    for candidate in flags.flat_map(|flag| [flag.display_name(), flag.name()]) {
+        let distance = match edit_distance(name.clone(), candidate) {
+            Some(distance) => distance,
+            None => continue,
         };
 
-        ParseError(ParseErrorKind::InvalidNamedFlag { got })
+        // Exact matches can only differ by case, but are still worth suggesting
+        if distance <= max_distance && nearest.map_or(true, |(_, nearest)| distance < nearest) {
+            nearest = Some((candidate, distance));
+        }
+    }
+
+    nearest.map(|(name, _)| name)
+}
+
+// The number of single character insertions, deletions, or substitutions needed to turn `a`
+// into `b`, ignoring ASCII case
+//
+// This returns `None` if `b` is too long to compare without allocating
+fn edit_distance(a: impl Iterator<Item = char>, b: &str) -> Option<usize> {
+    const MAX_LEN: usize = 64;
+
+    let len = b.chars().count();
+    if len >= MAX_LEN {
+        return None;
+    }
+
+    // The distances between the prefix of `a` processed so far and each prefix of `b`
+    let mut row = [0; MAX_LEN];
+    for (i, distance) in row.iter_mut().enumerate().take(len + 1) {
+        *distance = i;
+    }
+
+    for (i, a) in a.enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b) in b.chars().enumerate() {
+            let substitution = diagonal + if a.eq_ignore_ascii_case(&b) { 0 } else { 1 };
+
+            diagonal = row[j + 1];
+            row[j + 1] = core::cmp::min(substitution, core::cmp::min(row[j], row[j + 1]) + 1);
+        }
+    }
+
+    Some(row[len])
+}
+
+// The mask of all reserved bits, which are ignored when formatting and parsing
+fn reserved<B: Flags>() -> B::Bits {
+    B::RESERVED_ZERO | B::RESERVED_ONE
+}
+
+// Unset any reserved bits that must be unset, and set any that must be set
+pub(crate) fn fix_reserved<B: Flags>(flags: B) -> B {
+    B::from_bits_retain((flags.bits() & !reserved::<B>()) | B::RESERVED_ONE)
+}
+
+/**
+Options for the grammar used to format and parse flags values as text.
+
+The default options use the grammar described in the [module docs](index.html), which is
+the one used by [`to_writer`] and [`from_str`]. Options can be changed to accept other
+common formats with [`to_writer_with`] and [`from_str_with`]:
+
+```
+use bitflags::{bitflags, parser::{self, ParserOptions}};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+    }
+}
+
+let options = ParserOptions::new()
+    .case_sensitive(false)
+    .separators(&[',', '+'])
+    .trailing_separator(true)
+    .write_separator(", ");
+
+assert_eq!(
+    Permissions::READ | Permissions::WRITE,
+    parser::from_str_with("read, Write,", &options).unwrap(),
+);
+assert_eq!(
+    Permissions::READ | Permissions::WRITE,
+    parser::from_str_with("Read + Write", &options).unwrap(),
+);
+
+let mut formatted = String::new();
+parser::to_writer_with(&(Permissions::READ | Permissions::WRITE), &mut formatted, &options).unwrap();
+
+assert_eq!("READ, WRITE", formatted);
+```
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct ParserOptions<'a> {
+    case_sensitive: bool,
+    separators: &'a [char],
+    whitespace_separators: bool,
+    trailing_separator: bool,
+    write_separator: &'a str,
+    write_radix: Radix,
+    expressions: bool,
+    canonical: bool,
+    type_name: Option<&'a str>,
+    deprecated_aliases: bool,
+}
+
+impl<'a> ParserOptions<'a> {
+    /// Get the default options.
+    pub const fn new() -> Self {
+        ParserOptions {
+            case_sensitive: true,
+            separators: &['|'],
+            whitespace_separators: false,
+            trailing_separator: false,
+            write_separator: " | ",
+            write_radix: Radix::Hex,
+            expressions: false,
+            canonical: false,
+            type_name: None,
+            deprecated_aliases: true,
+        }
+    }
+
+    /// Whether names of flags are case-sensitive when parsing.
+    ///
+    /// The default is `true`. When names aren't case-sensitive, they're compared ignoring
+    /// ASCII case, and hex numbers may also use a `0X` prefix.
+    #[must_use]
+    pub const fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// The separators accepted between flags when parsing.
+    ///
+    /// The default is `|`.
+    #[must_use]
+    pub const fn separators(mut self, separators: &'a [char]) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Whether whitespace is accepted as a separator between flags when parsing.
+    ///
+    /// The default is `false`. Any amount of whitespace is treated as a single separator.
+    /// Fields can't contain whitespace around their `=` when this is enabled. If expressions are
+    /// also enabled, whitespace around `-` and after `!` doesn't separate flags, so `all - A` is
+    /// still a single flag.
+    #[must_use]
+    pub const fn whitespace_separators(mut self, whitespace_separators: bool) -> Self {
+        self.whitespace_separators = whitespace_separators;
+        self
+    }
+
+    /// Whether a separator is accepted after the last flag when parsing.
+    ///
+    /// The default is `false`.
+    #[must_use]
+    pub const fn trailing_separator(mut self, trailing_separator: bool) -> Self {
+        self.trailing_separator = trailing_separator;
+        self
+    }
+
+    /// The separator written between flags when formatting.
+    ///
+    /// The default is ` | `. Parsing doesn't accept this separator unless it's also
+    /// given to [`ParserOptions::separators`].
+    #[must_use]
+    pub const fn write_separator(mut self, write_separator: &'a str) -> Self {
+        self.write_separator = write_separator;
+        self
+    }
+
+    /// The radix unknown bits are written in when formatting.
+    ///
+    /// The default is [`Radix::Hex`]. Bits types that only support hex numbers will
+    /// always be written in hex.
+    #[must_use]
+    pub const fn write_radix(mut self, write_radix: Radix) -> Self {
+        self.write_radix = write_radix;
+        self
+    }
+
+    /// Whether flags are parsed as expressions, like `all - A`.
+    ///
+    /// The default is `false`. See the [module docs](index.html#expressions) for the grammar
+    /// of expressions. The `-` operator can't be used if `-` is also a separator.
+    #[must_use]
+    pub const fn expressions(mut self, expressions: bool) -> Self {
+        self.expressions = expressions;
+        self
+    }
+
+    /// Whether flags are written in their canonical form when formatting.
+    ///
+    /// The default is `false`, which writes the name of each contained flag that sets
+    /// any bits not already written, in the order they're declared. The canonical form is the
+    /// smallest set of names that covers the same bits, so flags that set multiple bits are
+    /// preferred over their individual bits. The names are still written in the order they're
+    /// declared, so the same value is always formatted the same way.
+    ///
+    /// Finding the smallest set of names is expensive, so when more than 16 named flags are
+    /// contained in the value, a set is picked from the flags that aren't contained in any other
+    /// instead. This may not be the smallest set, but it never has a name whose bits are
+    /// covered by the others.
+    #[must_use]
+    pub const fn canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// The name of the flags type accepted when parsing.
+    ///
+    /// The default is `None`. When set, the input may be wrapped in the name of the type, like
+    /// the `Debug` output `MyFlags(A | B)`, and flags may be qualified by the type, like the
+    /// Rust expression `MyFlags::A | MyFlags::from_bits_retain(0x40)`. See the
+    /// [module docs](index.html#type-names) for details.
+    #[must_use]
+    pub const fn type_name(mut self, type_name: &'a str) -> Self {
+        self.type_name = Some(type_name);
+        self
+    }
+
+    /// Whether deprecated aliases of flags are accepted when parsing.
+    ///
+    /// The default is `true`. Aliases are declared with [`Flag::with_aliases`] and
+    /// [`Flag::with_deprecated_aliases`], or the `#[alias]` attribute in the
+    /// [`bitflags`](crate::bitflags) macro.
+    #[must_use]
+    pub const fn deprecated_aliases(mut self, deprecated_aliases: bool) -> Self {
+        self.deprecated_aliases = deprecated_aliases;
+        self
+    }
+
+    fn has_name<B>(&self, flag: &Flag<B>, name: &[u8]) -> bool {
+        has_name(flag, self.deprecated_aliases, |defined| {
+            self.name_eq(defined, name)
+        })
+    }
+
+    fn name_eq(&self, defined: &str, name: &[u8]) -> bool {
+        if self.case_sensitive {
+            defined.as_bytes() == name
+        } else {
+            defined.as_bytes().eq_ignore_ascii_case(name)
+        }
+    }
+}
+
+impl Default for ParserOptions<'_> {
+    fn default() -> Self {
+        ParserOptions::new()
+    }
+}
+
+/**
+The radix of a number in the text format.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Radix {
+    /// Base 2, with a `0b` prefix.
+    Binary,
+    /// Base 8, with a `0o` prefix.
+    Octal,
+    /// Base 10, with no prefix.
+    Decimal,
+    /// Base 16, with a `0x` prefix.
+    Hex,
+}
+
+impl Radix {
+    /// Get the base of the radix, like `16` for [`Radix::Hex`].
+    pub const fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hex => 16,
+        }
+    }
+
+    /// Get the prefix of numbers in the radix, like `0x` for [`Radix::Hex`].
+    ///
+    /// The prefix of [`Radix::Decimal`] is empty.
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+            Radix::Decimal => "",
+            Radix::Hex => "0x",
+        }
+    }
+}
+
+/**
+Encode a value as a hex string.
+
+Implementors of this trait should not write the `0x` prefix.
+*/
+pub trait WriteHex {
+    /// Write the value as hex.
+    fn write_hex<W: fmt::Write>(&self, writer: W) -> fmt::Result;
+
+    /// Write the value as a number in the given radix, including its prefix.
+    ///
+    /// The default implementation always writes the value as hex with a `0x` prefix,
+    /// so implementors only need to override it if they support other radixes.
+    fn write_radix<W: fmt::Write>(&self, mut writer: W, radix: Radix) -> fmt::Result {
+        let _ = radix;
+
+        writer.write_str("0x")?;
+        self.write_hex(writer)
+    }
+}
+
+/**
+Parse a value from a hex string.
+*/
+pub trait ParseHex {
+    /// Parse the value from hex.
+    fn parse_hex(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    /// Parse the value from a number in the given radix, without its prefix.
+    ///
+    /// The default implementation only supports [`Radix::Hex`], so implementors only need
+    /// to override it if they support other radixes.
+    fn parse_radix(input: &str, radix: Radix) -> Result<Self, ParseError>
+    where
+        Self: Sized,
+    {
+        match radix {
+            Radix::Hex => Self::parse_hex(input),
+            _ => Err(ParseError::invalid_number_flag(input)),
+        }
+    }
+
+    /// Parse the value from a number in the given radix, without its prefix, from ASCII text
+    /// in bytes.
+    ///
+    /// The default implementation converts the input to a string and calls
+    /// [`ParseHex::parse_radix`], so implementors can override it to avoid validating UTF-8.
+    fn parse_radix_bytes(input: &[u8], radix: Radix) -> Result<Self, ParseError>
+    where
+        Self: Sized,
+    {
+        match core::str::from_utf8(input) {
+            Ok(input) => Self::parse_radix(input, radix),
+            Err(_) => Err(ParseError::invalid_number_flag(AsciiDisplay(input))),
+        }
+    }
+}
+
+/// An error encountered while parsing flags from text.
+#[derive(Debug)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    #[cfg(feature = "alloc")]
+    got: Option<String>,
+    span: Option<Range<usize>>,
+    index: Option<usize>,
+    suggestion: Option<&'static str>,
+}
+
+/// The kind of error encountered while parsing flags from text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[allow(clippy::enum_variant_names)]
+pub enum ParseErrorKind {
+    /// A hex or named flag wasn't found between separators.
+    EmptyFlag,
+    /// A named flag that doesn't correspond to any on the flags type was encountered.
+    InvalidNamedFlag,
+    /// An invalid hex flag was encountered.
+    InvalidHexFlag,
+    /// An invalid binary, octal, or decimal flag was encountered.
+    InvalidNumberFlag,
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, flag: impl fmt::Display) -> Self {
+        let _flag = flag;
+
+        ParseError {
+            kind,
+            #[cfg(feature = "alloc")]
+            got: Some(_flag.to_string()),
+            span: None,
+            index: None,
+            suggestion: None,
+        }
+    }
+
+    /// An invalid hex flag was encountered.
+    pub fn invalid_hex_flag(flag: impl fmt::Display) -> Self {
+        ParseError::new(ParseErrorKind::InvalidHexFlag, flag)
+    }
+
+    /// An invalid binary, octal, or decimal flag was encountered.
+    pub fn invalid_number_flag(flag: impl fmt::Display) -> Self {
+        ParseError::new(ParseErrorKind::InvalidNumberFlag, flag)
+    }
+
+    /// A named flag that doesn't correspond to any on the flags type was encountered.
+    pub fn invalid_named_flag(flag: impl fmt::Display) -> Self {
+        ParseError::new(ParseErrorKind::InvalidNamedFlag, flag)
     }
 
     /// A hex or named flag wasn't found between separators.
     pub const fn empty_flag() -> Self {
-        ParseError(ParseErrorKind::EmptyFlag)
+        ParseError {
+            kind: ParseErrorKind::EmptyFlag,
+            #[cfg(feature = "alloc")]
+            got: None,
+            span: None,
+            index: None,
+            suggestion: None,
+        }
+    }
+
+    /// The kind of error.
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /**
+    The range of bytes in the input that caused the error.
+
+    This will be `None` if the error wasn't produced by one of the functions in this module.
+    */
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /**
+    The index of the item that caused the error, when parsing a sequence of flags.
+
+    This will be `None` unless the error was produced by [`from_names`] or one of its variants.
+    */
+    pub const fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /**
+    The name of a defined flag that was likely meant instead of an unrecognized one.
+
+    Suggestions are only made for [`ParseErrorKind::InvalidNamedFlag`] errors, when the name of
+    a defined flag is only a few characters different from the unrecognized one.
+    */
+    pub const fn suggestion(&self) -> Option<&'static str> {
+        self.suggestion
+    }
+
+    fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    fn with_suggestion(mut self, suggestion: &'static str) -> Self {
+        self.suggestion = Some(suggestion);
+        self
     }
 }
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.0 {
-            ParseErrorKind::InvalidNamedFlag { got } => {
-                let _got = got;
-
-                write!(f, "unrecognized named flag")?;
+        match self.kind {
+            ParseErrorKind::InvalidNamedFlag => write!(f, "unrecognized named flag")?,
+            ParseErrorKind::InvalidHexFlag => write!(f, "invalid hex flag")?,
+            ParseErrorKind::InvalidNumberFlag => write!(f, "invalid number flag")?,
+            ParseErrorKind::EmptyFlag => write!(f, "encountered empty flag")?,
+        }
 
-                #[cfg(feature = "std")]
-                {
-                    write!(f, " `{}`", _got)?;
-                }
+        #[cfg(feature = "alloc")]
+        {
+            if let Some(got) = &self.got {
+                write!(f, " `{}`", got)?;
             }
-            ParseErrorKind::InvalidHexFlag { got } => {
-                let _got = got;
+        }
 
-                write!(f, "invalid hex flag")?;
+        if let Some(span) = &self.span {
+            write!(f, " at {}..{}", span.start, span.end)?;
+        }
 
-                #[cfg(feature = "std")]
-                {
-                    write!(f, " `{}`", _got)?;
-                }
-            }
-            ParseErrorKind::EmptyFlag => {
-                write!(f, "encountered empty flag")?;
-            }
+        if let Some(index) = self.index {
+            write!(f, " in item {}", index)?;
+        }
+
+        if let Some(suggestion) = self.suggestion {
+            write!(f, ", did you mean `{}`?", suggestion)?;
         }
 
         Ok(())
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(bitflags_core_error)]
+impl core::error::Error for ParseError {}
+
+#[cfg(all(feature = "std", not(bitflags_core_error)))]
 impl std::error::Error for ParseError {}
+
+/**
+All errors encountered while parsing flags from text.
+
+This type is returned by [`from_str_collect_errors`].
+*/
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct ParseErrors(Vec<ParseError>);
+
+#[cfg(feature = "alloc")]
+impl ParseErrors {
+    /// The errors, in the order they appeared in the input.
+    pub fn errors(&self) -> &[ParseError] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl IntoIterator for ParseErrors {
+    type Item = ParseError;
+    type IntoIter = vec::IntoIter<ParseError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> IntoIterator for &'a ParseErrors {
+    type Item = &'a ParseError;
+    type IntoIter = core::slice::Iter<'a, ParseError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for error in &self.0 {
+            if !first {
+                f.write_str("; ")?;
+            }
+
+            first = false;
+            write!(f, "{}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "alloc", bitflags_core_error))]
+impl core::error::Error for ParseErrors {}
+
+#[cfg(all(feature = "std", not(bitflags_core_error)))]
+impl std::error::Error for ParseErrors {}
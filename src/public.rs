@@ -155,14 +155,20 @@ macro_rules! __impl_public_bitflags {
                             {{
                                 let flag = <$PublicBitFlags as $crate::Flags>::FLAGS[i].value().bits();
 
-                                truncated = truncated | flag;
+                                truncated = $crate::__private::ConstBits::<$T>::union(truncated, flag);
                                 i += 1;
                             }}
                         );
                     )*
 
                     let _ = i;
-                    Self::from_bits_retain(truncated)
+                    Self::from_bits_retain($crate::__private::ConstBits::<$T>::union(
+                        $crate::__private::ConstBits::<$T>::difference(
+                            truncated,
+                            <$PublicBitFlags as $crate::Flags>::RESERVED_ZERO,
+                        ),
+                        <$PublicBitFlags as $crate::Flags>::RESERVED_ONE,
+                    ))
                 }
 
                 fn bits(f) {
@@ -172,7 +178,7 @@ macro_rules! __impl_public_bitflags {
                 fn from_bits(bits) {
                     let truncated = Self::from_bits_truncate(bits).0;
 
-                    if truncated == bits {
+                    if $crate::__private::ConstBits::<$T>::eq(truncated, bits) {
                         $crate::__private::core::option::Option::Some(Self(bits))
                     } else {
                         $crate::__private::core::option::Option::None
@@ -180,7 +186,10 @@ macro_rules! __impl_public_bitflags {
                 }
 
                 fn from_bits_truncate(bits) {
-                    Self(bits & Self::all().bits())
+                    Self($crate::__private::ConstBits::<$T>::union(
+                        $crate::__private::ConstBits::<$T>::intersection(bits, Self::all().bits()),
+                        <$PublicBitFlags as $crate::Flags>::RESERVED_ONE,
+                    ))
                 }
 
                 fn from_bits_retain(bits) {
@@ -210,21 +219,30 @@ macro_rules! __impl_public_bitflags {
                 }
 
                 fn is_empty(f) {
-                    f.bits() == <$T as $crate::Bits>::EMPTY
+                    $crate::__private::ConstBits::<$T>::eq(f.bits(), <$T as $crate::Bits>::EMPTY)
                 }
 
                 fn is_all(f) {
                     // NOTE: We check against `Self::all` here, not `Self::Bits::ALL`
                     // because the set of all flags may not use all bits
-                    Self::all().bits() | f.bits() == f.bits()
+                    $crate::__private::ConstBits::<$T>::eq(
+                        $crate::__private::ConstBits::<$T>::union(Self::all().bits(), f.bits()),
+                        f.bits(),
+                    )
                 }
 
                 fn intersects(f, other) {
-                    f.bits() & other.bits() != <$T as $crate::Bits>::EMPTY
+                    !$crate::__private::ConstBits::<$T>::eq(
+                        $crate::__private::ConstBits::<$T>::intersection(f.bits(), other.bits()),
+                        <$T as $crate::Bits>::EMPTY,
+                    )
                 }
 
                 fn contains(f, other) {
-                    f.bits() & other.bits() == other.bits()
+                    $crate::__private::ConstBits::<$T>::eq(
+                        $crate::__private::ConstBits::<$T>::intersection(f.bits(), other.bits()),
+                        other.bits(),
+                    )
                 }
 
                 fn insert(f, other) {
@@ -248,23 +266,23 @@ macro_rules! __impl_public_bitflags {
                 }
 
                 fn intersection(f, other) {
-                    Self::from_bits_retain(f.bits() & other.bits())
+                    Self::from_bits_retain($crate::__private::ConstBits::<$T>::intersection(f.bits(), other.bits()))
                 }
 
                 fn union(f, other) {
-                    Self::from_bits_retain(f.bits() | other.bits())
+                    Self::from_bits_retain($crate::__private::ConstBits::<$T>::union(f.bits(), other.bits()))
                 }
 
                 fn difference(f, other) {
-                    Self::from_bits_retain(f.bits() & !other.bits())
+                    Self::from_bits_retain($crate::__private::ConstBits::<$T>::difference(f.bits(), other.bits()))
                 }
 
                 fn symmetric_difference(f, other) {
-                    Self::from_bits_retain(f.bits() ^ other.bits())
+                    Self::from_bits_retain($crate::__private::ConstBits::<$T>::symmetric_difference(f.bits(), other.bits()))
                 }
 
                 fn complement(f) {
-                    Self::from_bits_truncate(!f.bits())
+                    Self::from_bits_truncate($crate::__private::ConstBits::<$T>::complement(f.bits()))
                 }
             }
         }
@@ -518,12 +536,10 @@ macro_rules! __impl_public_bitflags_consts {
                 $crate::__bitflags_flag!({
                     name: $Flag,
                     named: {
-                        $(#[$inner $($args)*])*
-                        #[allow(
-                            deprecated,
-                            non_upper_case_globals,
-                        )]
-                        pub const $Flag: Self = Self::from_bits_retain($value);
+                        $crate::__bitflags_field! {
+                            const { $PublicBitFlags, $Flag = $value }
+                            $(#[$inner $($args)*])*
+                        }
                     },
                     unnamed: {},
                 });
@@ -540,11 +556,10 @@ macro_rules! __impl_public_bitflags_consts {
                             $crate::__bitflags_expr_safe_attrs!(
                                 $(#[$inner $($args)*])*
                                 {
-                                    #[allow(
-                                        deprecated,
-                                        non_upper_case_globals,
-                                    )]
-                                    $crate::Flag::new($crate::__private::core::stringify!($Flag), $PublicBitFlags::$Flag)
+                                    $crate::__bitflags_field! {
+                                        flag { $PublicBitFlags, $Flag }
+                                        $(#[$inner $($args)*])*
+                                    }
                                 }
                             )
                         },
@@ -566,6 +581,36 @@ macro_rules! __impl_public_bitflags_consts {
 
             type Bits = $T;
 
+            const RESERVED_ZERO: $T = {
+                #[allow(unused_mut)]
+                let mut reserved = <$T as $crate::Bits>::EMPTY;
+
+                $(
+                    $crate::__bitflags_reserved!(
+                        zero,
+                        $(#[$inner $($args)*])*
+                        { reserved = $crate::__private::ConstBits::<$T>::union(reserved, $value) }
+                    );
+                )*
+
+                reserved
+            };
+
+            const RESERVED_ONE: $T = {
+                #[allow(unused_mut)]
+                let mut reserved = <$T as $crate::Bits>::EMPTY;
+
+                $(
+                    $crate::__bitflags_reserved!(
+                        one,
+                        $(#[$inner $($args)*])*
+                        { reserved = $crate::__private::ConstBits::<$T>::union(reserved, $value) }
+                    );
+                )*
+
+                reserved
+            };
+
             fn bits(&self) -> $T {
                 $PublicBitFlags::bits(self)
             }
@@ -574,5 +619,18 @@ macro_rules! __impl_public_bitflags_consts {
                 $PublicBitFlags::from_bits_retain(bits)
             }
         }
+
+        $(
+            $crate::__bitflags_flag!({
+                name: $Flag,
+                named: {
+                    $crate::__bitflags_field! {
+                        impl { $PublicBitFlags: $T, $Flag }
+                        $(#[$inner $($args)*])*
+                    }
+                },
+                unnamed: {},
+            });
+        )*
     };
 }
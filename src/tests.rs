@@ -1,13 +1,20 @@
 mod all;
+#[cfg(feature = "atomic")]
+mod atomic;
 mod bitflags_match;
 mod bits;
+mod bytes;
 mod complement;
 mod contains;
 mod difference;
 mod empty;
 mod eq;
+#[cfg(feature = "atomic")]
+mod event;
 mod extend;
+mod field;
 mod flags;
+mod flags_macro;
 mod fmt;
 mod from_bits;
 mod from_bits_retain;
@@ -19,12 +26,19 @@ mod intersects;
 mod is_all;
 mod is_empty;
 mod iter;
+mod names;
 mod parser;
 mod remove;
+mod reserved;
 mod symmetric_difference;
 mod truncate;
 mod union;
 mod unknown;
+#[cfg(feature = "volatile")]
+mod volatile;
+mod words;
+#[cfg(feature = "zerocopy")]
+mod zerocopy;
 
 bitflags! {
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -63,6 +77,15 @@ bitflags! {
         const ZERO = 0;
     }
 
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+    pub struct TestMultiByte: u32 {
+        /// 1
+        const A = 1;
+
+        /// 1 << 9
+        const B = 1 << 9;
+    }
+
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
     pub struct TestZeroOne: u8 {
         /// 0
@@ -126,9 +149,82 @@ bitflags! {
         const _ = !0;
     }
 
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+    pub struct TestReserved: u8 {
+        /// 1
+        const A = 1;
+
+        /// 1 << 1
+        const B = 1 << 1;
+
+        /// Must be zero
+        #[reserved(zero)]
+        const _ = 1 << 7;
+
+        /// Must be one
+        #[reserved(one)]
+        const _ = 1 << 6;
+
+        /// External
+        const _ = !0;
+    }
+
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+    pub struct TestField: u8 {
+        /// 1
+        const A = 1;
+
+        /// (1 << 1) | (1 << 2)
+        #[field(TestMode)]
+        const MODE = 0b0000_0110;
+
+        /// 1 << 3
+        const B = 1 << 3;
+    }
+
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+    pub struct TestNames: u8 {
+        /// 1
+        #[display("read-only")]
+        #[alias("ro")]
+        #[alias("READONLY", deprecated)]
+        const READ_ONLY = 1;
+
+        /// 1 << 1
+        #[display("exec")]
+        const EXEC = 1 << 1;
+
+        /// 1 << 2
+        const OTHER = 1 << 2;
+
+        /// (1 << 3) | (1 << 4)
+        #[field(TestMode)]
+        #[display("mode")]
+        const MODE = 0b0001_1000;
+    }
+
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
     pub struct TestExternalFull: u8 {
         /// External
         const _ = !0;
     }
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TestMode {
+    Slow = 0,
+    Fast = 1,
+    Turbo = 2,
+}
+
+impl crate::FieldValue<u8> for TestMode {
+    const VARIANTS: &'static [(&'static str, TestMode)] = &[
+        ("Slow", TestMode::Slow),
+        ("Fast", TestMode::Fast),
+        ("Turbo", TestMode::Turbo),
+    ];
+
+    fn to_bits(&self) -> u8 {
+        *self as u8
+    }
+}
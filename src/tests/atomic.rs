@@ -0,0 +1,91 @@
+use core::sync::atomic::Ordering;
+use std::{sync::Arc, thread};
+
+use crate::atomic::Atomic;
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Color: u8 {
+        const RED = 0x1;
+        const GREEN = 0x2;
+        const BLUE = 0x4;
+    }
+}
+
+#[test]
+fn cases() {
+    let color = Atomic::new(Color::RED);
+
+    assert_eq!(
+        Color::RED,
+        color.fetch_insert(Color::GREEN, Ordering::SeqCst)
+    );
+    assert_eq!(
+        Color::RED | Color::GREEN,
+        color.fetch_toggle(Color::RED | Color::BLUE, Ordering::SeqCst)
+    );
+    assert_eq!(
+        Color::GREEN | Color::BLUE,
+        color.fetch_remove(Color::GREEN, Ordering::SeqCst)
+    );
+    assert_eq!(Color::BLUE, color.load(Ordering::SeqCst));
+
+    assert_eq!(
+        Err(Color::BLUE),
+        color.compare_exchange(Color::RED, Color::GREEN, Ordering::SeqCst, Ordering::SeqCst)
+    );
+    assert_eq!(
+        Ok(Color::BLUE),
+        color.compare_exchange(
+            Color::BLUE,
+            Color::GREEN,
+            Ordering::SeqCst,
+            Ordering::SeqCst
+        )
+    );
+
+    assert_eq!(
+        Err(Color::GREEN),
+        color.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |_| None)
+    );
+    assert_eq!(
+        Ok(Color::GREEN),
+        color.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |c| Some(c | Color::RED))
+    );
+
+    assert_eq!(Color::RED | Color::GREEN, color.into_inner());
+}
+
+#[test]
+fn unknown_bits() {
+    let color = Atomic::new(Color::from_bits_retain(0x80));
+
+    color.fetch_remove(Color::from_bits_retain(0x81), Ordering::SeqCst);
+    assert_eq!(Color::empty(), color.load(Ordering::SeqCst));
+}
+
+#[test]
+#[cfg(not(miri))] // Very slow in miri
+fn threads() {
+    let color = Arc::new(Atomic::<Color>::default());
+
+    let handles = [Color::RED, Color::GREEN, Color::BLUE]
+        .iter()
+        .map(|flag| {
+            let color = color.clone();
+            let flag = *flag;
+
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    color.fetch_insert(flag, Ordering::AcqRel);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(Color::all(), color.load(Ordering::Acquire));
+}
@@ -0,0 +1,182 @@
+use super::*;
+
+use crate::{bytes::*, Flags};
+
+#[test]
+fn to_bytes() {
+    assert_eq!([0b0000_0101], to_le_bytes(&(TestFlags::A | TestFlags::C)));
+    assert_eq!([0b0000_0101], to_be_bytes(&(TestFlags::A | TestFlags::C)));
+
+    assert_eq!(
+        [1, 0b10, 0, 0],
+        to_le_bytes(&(TestMultiByte::A | TestMultiByte::B))
+    );
+    assert_eq!(
+        [0, 0, 0b10, 1],
+        to_be_bytes(&(TestMultiByte::A | TestMultiByte::B))
+    );
+
+    assert_eq!([0b0100_0000], msb0::to_le_bytes(&TestFlags::B));
+    assert_eq!([0b0100_0000], msb0::to_be_bytes(&TestFlags::B));
+
+    assert_eq!(
+        [0, 0, 0b0100_0000, 0b1000_0000],
+        msb0::to_le_bytes(&(TestMultiByte::A | TestMultiByte::B))
+    );
+    assert_eq!(
+        [0b1000_0000, 0b0100_0000, 0, 0],
+        msb0::to_be_bytes(&(TestMultiByte::A | TestMultiByte::B))
+    );
+}
+
+#[test]
+fn from_bytes() {
+    assert_eq!(Some(TestFlags::ABC), from_le_bytes([0b0000_0111]));
+    assert_eq!(Some(TestFlags::ABC), from_be_bytes([0b0000_0111]));
+    assert_eq!(None, from_le_bytes::<TestFlags>([0b1000_0001]));
+    assert_eq!(None, from_be_bytes::<TestFlags>([0b1000_0001]));
+
+    assert_eq!(
+        Some(TestMultiByte::A | TestMultiByte::B),
+        from_le_bytes([1, 0b10, 0, 0])
+    );
+    assert_eq!(
+        Some(TestMultiByte::A | TestMultiByte::B),
+        from_be_bytes([0, 0, 0b10, 1])
+    );
+    assert_eq!(None, from_be_bytes::<TestMultiByte>([1, 0b10, 0, 0]));
+
+    assert_eq!(Some(TestFlags::B), msb0::from_le_bytes([0b0100_0000]));
+    assert_eq!(Some(TestFlags::B), msb0::from_be_bytes([0b0100_0000]));
+    assert_eq!(None, msb0::from_be_bytes::<TestFlags>([0b0000_0001]));
+
+    assert_eq!(
+        Some(TestMultiByte::A | TestMultiByte::B),
+        msb0::from_le_bytes([0, 0, 0b0100_0000, 0b1000_0000])
+    );
+    assert_eq!(
+        Some(TestMultiByte::A | TestMultiByte::B),
+        msb0::from_be_bytes([0b1000_0000, 0b0100_0000, 0, 0])
+    );
+}
+
+#[test]
+fn from_bytes_truncate() {
+    assert_eq!(TestFlags::A, from_le_bytes_truncate([0b1000_0001]));
+    assert_eq!(TestFlags::A, from_be_bytes_truncate([0b1000_0001]));
+
+    assert_eq!(TestFlags::C, msb0::from_le_bytes_truncate([0b0010_0001]));
+    assert_eq!(TestFlags::C, msb0::from_be_bytes_truncate([0b0010_0001]));
+}
+
+#[test]
+fn from_bytes_retain() {
+    assert_eq!(
+        TestFlags::from_bits_retain(0b1000_0001),
+        from_le_bytes_retain([0b1000_0001])
+    );
+    assert_eq!(
+        TestFlags::from_bits_retain(0b1000_0001),
+        from_be_bytes_retain([0b1000_0001])
+    );
+
+    assert_eq!(
+        TestFlags::from_bits_retain(0b1000_0100),
+        msb0::from_le_bytes_retain([0b0010_0001])
+    );
+    assert_eq!(
+        TestFlags::from_bits_retain(0b1000_0100),
+        msb0::from_be_bytes_retain([0b0010_0001])
+    );
+}
+
+#[test]
+#[cfg(not(miri))] // Very slow in miri
+fn roundtrip() {
+    for bits in 0u8..=255 {
+        let flags = TestFlags::from_bits_retain(bits);
+
+        assert_eq!(flags, from_le_bytes_retain(to_le_bytes(&flags)));
+        assert_eq!(flags, from_be_bytes_retain(to_be_bytes(&flags)));
+        assert_eq!(flags, msb0::from_le_bytes_retain(msb0::to_le_bytes(&flags)));
+        assert_eq!(flags, msb0::from_be_bytes_retain(msb0::to_be_bytes(&flags)));
+    }
+}
+
+#[test]
+fn flags_methods() {
+    let flags = TestMultiByte::A | TestMultiByte::B;
+
+    assert_eq!([1, 0b10, 0, 0], Flags::to_le_bytes(&flags));
+    assert_eq!([0, 0, 0b10, 1], Flags::to_be_bytes(&flags));
+
+    assert_eq!(
+        Some(flags),
+        <TestMultiByte as Flags>::from_le_bytes([1, 0b10, 0, 0])
+    );
+    assert_eq!(
+        Some(flags),
+        <TestMultiByte as Flags>::from_be_bytes([0, 0, 0b10, 1])
+    );
+    assert_eq!(
+        None,
+        <TestMultiByte as Flags>::from_le_bytes([1, 0b10, 0, 0b1000_0000])
+    );
+
+    assert_eq!(
+        flags,
+        <TestMultiByte as Flags>::from_le_bytes_truncate([1, 0b10, 0, 0b1000_0000])
+    );
+    assert_eq!(
+        flags,
+        <TestMultiByte as Flags>::from_be_bytes_truncate([0b1000_0000, 0, 0b10, 1])
+    );
+
+    assert_eq!(
+        TestMultiByte::from_bits_retain(1 << 31) | flags,
+        <TestMultiByte as Flags>::from_le_bytes_retain([1, 0b10, 0, 0b1000_0000])
+    );
+    assert_eq!(
+        TestMultiByte::from_bits_retain(1 << 31) | flags,
+        <TestMultiByte as Flags>::from_be_bytes_retain([0b1000_0000, 0, 0b10, 1])
+    );
+}
+
+#[test]
+fn words() {
+    use crate::Words;
+
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Wide: Words<2> {
+            const A = Words::bit(0);
+            const B = Words::bit(64 + 8);
+        }
+    }
+
+    let flags = Wide::A | Wide::B;
+
+    let mut le = [0; 16];
+    le[0] = 1;
+    le[9] = 1;
+
+    let mut be = le;
+    be.reverse();
+
+    assert_eq!(le, flags.to_le_bytes());
+    assert_eq!(be, flags.to_be_bytes());
+
+    assert_eq!(Some(flags), Wide::from_le_bytes(le));
+    assert_eq!(Some(flags), Wide::from_be_bytes(be));
+
+    le[15] = 1;
+    assert_eq!(None, Wide::from_le_bytes(le));
+    assert_eq!(flags, Wide::from_le_bytes_truncate(le));
+
+    let mut msb0_be = [0; 16];
+    msb0_be[0] = 0b1000_0000;
+    msb0_be[9] = 0b1000_0000;
+
+    assert_eq!(msb0_be, msb0::to_be_bytes(&flags));
+    assert_eq!(Some(flags), msb0::from_be_bytes(msb0_be));
+}
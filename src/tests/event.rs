@@ -0,0 +1,225 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll, Waker},
+};
+use std::{
+    sync::Arc,
+    task::Wake,
+    thread::{self, Thread},
+};
+
+use crate::event::{EventGroup, Wait};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Events: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+#[derive(Default)]
+struct CountWaker(AtomicUsize);
+
+impl CountWaker {
+    fn count(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl Wake for CountWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn poll_once<F: Future + Unpin>(future: &mut F, waker: &Arc<CountWaker>) -> Poll<F::Output> {
+    let waker = Waker::from(waker.clone());
+
+    Pin::new(future).poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn try_wait() {
+    let events = EventGroup::new(Events::A);
+
+    assert_eq!(None, events.try_wait(Wait::all(Events::A | Events::B)));
+    assert_eq!(
+        Some(Events::A),
+        events.try_wait(Wait::any(Events::A | Events::B))
+    );
+    assert_eq!(Events::A, events.get());
+
+    assert_eq!(
+        Some(Events::A),
+        events.try_wait(Wait::any(Events::A).and_clear())
+    );
+    assert_eq!(Events::empty(), events.get());
+}
+
+#[test]
+fn empty_mask() {
+    let events = EventGroup::new(Events::empty());
+
+    assert_eq!(
+        Some(Events::empty()),
+        events.try_wait(Wait::any(Events::empty()))
+    );
+    assert_eq!(
+        Some(Events::empty()),
+        events.try_wait(Wait::all(Events::empty()))
+    );
+
+    let waker = Arc::new(CountWaker::default());
+    assert_eq!(
+        Poll::Ready(Events::empty()),
+        poll_once(&mut events.wait_async(Wait::any(Events::empty())), &waker)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn wait_timeout() {
+    let events = EventGroup::new(Events::A);
+
+    assert_eq!(
+        None,
+        events.wait_timeout(Wait::all(Events::B), std::time::Duration::from_millis(1))
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn wait_blocking() {
+    let events = Arc::new(EventGroup::<Events>::default());
+
+    let waiter = {
+        let events = events.clone();
+
+        thread::spawn(move || events.wait(Wait::all(Events::A | Events::C).and_clear()))
+    };
+
+    events.set(Events::A);
+    events.set(Events::B);
+    events.set(Events::C);
+
+    assert_eq!(Events::all(), waiter.join().unwrap());
+    assert_eq!(Events::B, events.get());
+}
+
+#[test]
+fn wait_async() {
+    let events = Arc::new(EventGroup::new(Events::empty()));
+
+    let producer = {
+        let events = events.clone();
+
+        thread::spawn(move || {
+            events.set(Events::B);
+            events.clear(Events::B);
+            events.set(Events::C);
+        })
+    };
+
+    assert_eq!(
+        Events::C,
+        block_on(events.wait_async(Wait::any(Events::A | Events::C)))
+    );
+
+    producer.join().unwrap();
+}
+
+#[test]
+fn wait_async_wakes() {
+    let events = EventGroup::new(Events::empty());
+    let waker = Arc::new(CountWaker::default());
+
+    let mut future = events.wait_async(Wait::all(Events::A | Events::B).and_clear());
+
+    assert_eq!(Poll::Pending, poll_once(&mut future, &waker));
+    assert_eq!(0, waker.count());
+
+    events.set(Events::A);
+    assert_eq!(1, waker.count());
+    assert_eq!(Poll::Pending, poll_once(&mut future, &waker));
+
+    events.set(Events::B);
+    assert_eq!(2, waker.count());
+    assert_eq!(
+        Poll::Ready(Events::A | Events::B),
+        poll_once(&mut future, &waker)
+    );
+    assert_eq!(Events::empty(), events.get());
+
+    // Completed futures aren't woken again
+    events.set(Events::C);
+    assert_eq!(2, waker.count());
+}
+
+#[test]
+fn wait_async_drop() {
+    let events = EventGroup::<Events, 1>::with_waiters(Events::empty());
+
+    let dropped = Arc::new(CountWaker::default());
+    let mut future = events.wait_async(Wait::all(Events::A));
+    assert_eq!(Poll::Pending, poll_once(&mut future, &dropped));
+    drop(future);
+
+    // The dropped future freed its slot, so this one doesn't need to wake itself
+    let waker = Arc::new(CountWaker::default());
+    let mut future = events.wait_async(Wait::all(Events::A));
+    assert_eq!(Poll::Pending, poll_once(&mut future, &waker));
+    assert_eq!(0, waker.count());
+
+    events.set(Events::A);
+    assert_eq!(0, dropped.count());
+    assert_eq!(1, waker.count());
+    assert_eq!(Poll::Ready(Events::A), poll_once(&mut future, &waker));
+}
+
+#[test]
+fn wait_async_full() {
+    let events = EventGroup::<Events, 1>::with_waiters(Events::empty());
+
+    let first = Arc::new(CountWaker::default());
+    let mut first_future = events.wait_async(Wait::all(Events::A));
+    assert_eq!(Poll::Pending, poll_once(&mut first_future, &first));
+
+    // There are no free slots, so the second future wakes itself to be polled again
+    let second = Arc::new(CountWaker::default());
+    let mut second_future = events.wait_async(Wait::all(Events::A));
+    assert_eq!(Poll::Pending, poll_once(&mut second_future, &second));
+    assert_eq!(1, second.count());
+
+    events.set(Events::A);
+    assert_eq!(1, first.count());
+    assert_eq!(
+        Poll::Ready(Events::A),
+        poll_once(&mut second_future, &second)
+    );
+    assert_eq!(Poll::Ready(Events::A), poll_once(&mut first_future, &first));
+}
@@ -0,0 +1,175 @@
+use super::*;
+
+use crate::{parser::*, Flags, Words};
+
+impl crate::FieldValue<i8> for TestMode {
+    const VARIANTS: &'static [(&'static str, TestMode)] = <Self as crate::FieldValue<u8>>::VARIANTS;
+
+    fn to_bits(&self) -> i8 {
+        *self as i8
+    }
+}
+
+impl crate::FieldValue<Words<2>> for TestMode {
+    const VARIANTS: &'static [(&'static str, TestMode)] = <Self as crate::FieldValue<u8>>::VARIANTS;
+
+    fn to_bits(&self) -> Words<2> {
+        Words::from_words([*self as u64, 0])
+    }
+}
+
+#[test]
+fn get_field() {
+    case(Some(TestMode::Slow), 0);
+    case(Some(TestMode::Slow), 0b1111_1001);
+    case(Some(TestMode::Fast), 0b0000_0010);
+    case(Some(TestMode::Turbo), 0b0000_1101);
+    case(None, 0b0000_0110);
+
+    #[track_caller]
+    fn case(expected: Option<TestMode>, input: u8) {
+        assert_eq!(
+            expected,
+            TestField::from_bits_retain(input).get_field::<TestMode>(),
+            "{:?}.get_field()",
+            input
+        );
+    }
+}
+
+#[test]
+fn set_field() {
+    case(0b0000_0010, 0, TestMode::Fast);
+    case(0b0000_1101, 0b0000_1111, TestMode::Turbo);
+    case(0b1000_0001, 0b1000_0111, TestMode::Slow);
+
+    #[track_caller]
+    fn case(expected: u8, input: u8, value: TestMode) {
+        let mut flags = TestField::from_bits_retain(input);
+        flags.set_field(value);
+
+        assert_eq!(expected, flags.bits(), "{:?}.set_field({:?})", input, value);
+    }
+}
+
+#[test]
+fn signed() {
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Signed: i8 {
+            const A = 1;
+
+            #[field(TestMode)]
+            const MODE = 0b1100_0000u8 as i8;
+        }
+    }
+
+    // The sign bit is part of the field, so it mustn't be extended when shifting
+    let mut flags = Signed::A;
+    flags.set_field(TestMode::Turbo);
+
+    assert_eq!(0b1000_0001u8 as i8, flags.bits());
+    assert_eq!(Some(TestMode::Turbo), flags.get_field::<TestMode>());
+    assert_eq!(
+        Some(TestMode::Fast),
+        Signed::from_bits_retain(0b0100_0000).get_field::<TestMode>()
+    );
+    assert_eq!(None, Signed::from_bits_retain(-1).get_field::<TestMode>());
+}
+
+#[test]
+fn words() {
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Wide: Words<2> {
+            const A = Words::bit(0);
+
+            // Straddles the boundary between the two words
+            #[field(TestMode)]
+            const MODE = Words::bit(63).union(Words::bit(64));
+        }
+    }
+
+    let mut flags = Wide::A;
+    flags.set_field(TestMode::Fast);
+
+    assert_eq!(Words::from_words([1 | 1 << 63, 0]), flags.bits());
+    assert_eq!(Some(TestMode::Fast), flags.get_field::<TestMode>());
+
+    flags.set_field(TestMode::Turbo);
+
+    assert_eq!(Words::from_words([1, 1]), flags.bits());
+    assert_eq!(Some(TestMode::Turbo), flags.get_field::<TestMode>());
+}
+
+#[test]
+fn flags() {
+    let fields = TestField::FLAGS
+        .iter()
+        .filter(|flag| flag.is_field())
+        .map(|flag| flag.name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["MODE"], fields);
+    assert_eq!(0b0000_1111, TestField::all().bits());
+    assert_eq!(Some(TestField::MODE), TestField::from_name("MODE"));
+}
+
+#[test]
+fn field_variants() {
+    let mode = TestField::FLAGS
+        .iter()
+        .find(|flag| flag.is_field())
+        .unwrap();
+    assert_eq!(
+        vec!["Slow", "Fast", "Turbo"],
+        mode.field_variants().collect::<Vec<_>>()
+    );
+
+    let a = TestField::FLAGS
+        .iter()
+        .find(|flag| flag.name() == "A")
+        .unwrap();
+    assert_eq!(0, a.field_variants().count());
+}
+
+#[test]
+fn parser() {
+    case("", 0);
+    case("A", 0b0000_0001);
+    case("MODE=Fast", 0b0000_0010);
+    case("MODE=Turbo | A | B", 0b0000_1101);
+    case("MODE=Fast | 0x80", 0b1000_0010);
+    case("0x6", 0b0000_0110);
+
+    assert_eq!(
+        0b0000_0101,
+        from_str::<TestField>("A|MODE = Turbo").unwrap().bits()
+    );
+    assert_eq!(
+        0b0000_0101,
+        from_str_strict::<TestField>("A | MODE=Turbo")
+            .unwrap()
+            .bits()
+    );
+
+    assert!(from_str::<TestField>("MODE=Fastest").is_err());
+    assert!(from_str::<TestField>("A=Fast").is_err());
+    assert!(from_str_strict::<TestField>("MODE=").is_err());
+
+    #[track_caller]
+    fn case(expected: &str, input: u8) {
+        let flags = TestField::from_bits_retain(input);
+
+        let mut s = String::new();
+        to_writer(&flags, &mut s).unwrap();
+
+        assert_eq!(expected, s, "to_writer({:?})", input);
+        assert_eq!(
+            flags,
+            from_str::<TestField>(&s).unwrap(),
+            "from_str({:?})",
+            s
+        );
+    }
+}
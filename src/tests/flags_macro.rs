@@ -0,0 +1,114 @@
+use super::*;
+
+#[test]
+fn cases() {
+    const EMPTY: TestFlags = flags!(TestFlags, "");
+    const BLANK: TestFlags = flags!(TestFlags, "  ");
+    const A: TestFlags = flags!(TestFlags, "A");
+    const AB: TestFlags = flags!(TestFlags, " A|B ");
+    const ABC: TestFlags = flags!(TestFlags, "ABC");
+    const NUMBERS: TestFlags = flags!(TestFlags, "0x10 | 0b10_0000 | 0o100 | 128 | 0x0F");
+
+    assert_eq!(0, EMPTY.bits());
+    assert_eq!(0, BLANK.bits());
+    assert_eq!(1, A.bits());
+    assert_eq!(1 | 1 << 1, AB.bits());
+    assert_eq!(1 | 1 << 1 | 1 << 2, ABC.bits());
+    assert_eq!(0xff, NUMBERS.bits());
+
+    assert_eq!(
+        TestFlags::A | TestFlags::from_bits_retain(0x10),
+        flags!(TestFlags, "A | 0x10")
+    );
+}
+
+#[test]
+fn matches_from_str() {
+    for (expected, input) in [
+        (flags!(TestFlags, "A | B"), "A | B"),
+        (flags!(TestFlags, "C | 0xf0"), "C | 0xf0"),
+        (flags!(TestFlags, "A|B|C|ABC"), "A|B|C|ABC"),
+    ] {
+        assert_eq!(
+            crate::parser::from_str::<TestFlags>(input).unwrap(),
+            expected,
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn names() {
+    assert_eq!(
+        TestNames::READ_ONLY | TestNames::EXEC,
+        flags!(TestNames, "read-only | EXEC")
+    );
+    assert_eq!(TestNames::READ_ONLY, flags!(TestNames, "ro"));
+    assert_eq!(TestNames::READ_ONLY, flags!(TestNames, "READONLY"));
+}
+
+#[test]
+fn reserved() {
+    assert_eq!(0b0100_0001, flags!(TestReserved, "A").bits());
+    assert_eq!(0b0110_0001, flags!(TestReserved, "A | 0xa0").bits());
+}
+
+#[test]
+fn unicode() {
+    assert_eq!(1 | 1 << 1, flags!(TestUnicode, "一 | 二").bits());
+}
+
+#[test]
+fn multi_byte() {
+    assert_eq!(1 | 1 << 9, flags!(TestMultiByte, "A | 0x200").bits());
+}
+
+#[test]
+#[cfg(bitflags_const_from_utf8)]
+fn errors() {
+    use crate::{
+        __private::{ConstFlag, ConstParser},
+        Flags,
+    };
+
+    // The message that `flags!` fails the build with
+    fn error<const N: usize>(input: &'static str) -> String {
+        let mut parser = ConstParser::new(input, 1);
+
+        loop {
+            let (next, flag) = parser.next(TestFlags::FLAGS);
+            parser = next;
+
+            match flag {
+                ConstFlag::End => panic!("`{}` was parsed", input),
+                ConstFlag::Error(kind) => return parser.error::<N>(kind).as_str().to_owned(),
+                _ => (),
+            }
+        }
+    }
+
+    assert_eq!(
+        "failed to parse `A | D`: unrecognized named flag `D` at 4..5",
+        error::<128>("A | D")
+    );
+    assert_eq!(
+        "failed to parse `A || B`: encountered empty flag at 3..3",
+        error::<128>("A || B")
+    );
+    assert_eq!(
+        "failed to parse `A | 0xg`: invalid hex flag `0xg` at 4..7",
+        error::<128>("A | 0xg")
+    );
+    assert_eq!(
+        "failed to parse `0b2`: invalid number flag `0b2` at 0..3",
+        error::<128>("0b2")
+    );
+    assert_eq!(
+        "failed to parse `A | 一`: unrecognized named flag `一` at 4..7",
+        error::<128>("A | 一")
+    );
+
+    // Parts that don't fit are left out
+    assert_eq!("A | D`: ", error::<8>("A | D"));
+}
@@ -0,0 +1,146 @@
+use super::*;
+
+use crate::{parser::*, Flag, Flags};
+
+#[test]
+fn flags() {
+    let flag = &TestNames::FLAGS[0];
+    assert_eq!("READ_ONLY", flag.name());
+    assert_eq!("read-only", flag.display_name());
+    assert_eq!(&["ro"], flag.aliases());
+    assert_eq!(&["READONLY"], flag.deprecated_aliases());
+
+    let flag = &TestNames::FLAGS[2];
+    assert_eq!("OTHER", flag.name());
+    assert_eq!("OTHER", flag.display_name());
+    assert!(flag.aliases().is_empty());
+    assert!(flag.deprecated_aliases().is_empty());
+
+    let flag = &TestNames::FLAGS[3];
+    assert!(flag.is_field());
+    assert_eq!("mode", flag.display_name());
+
+    const FLAG: Flag<u8> = Flag::new("A", 1)
+        .with_display_name("a")
+        .with_aliases(&["alpha"])
+        .with_deprecated_aliases(&["first"]);
+
+    assert_eq!("A", FLAG.name());
+    assert_eq!("a", FLAG.display_name());
+    assert_eq!(&["alpha"], FLAG.aliases());
+    assert_eq!(&["first"], FLAG.deprecated_aliases());
+}
+
+#[test]
+fn iter_names() {
+    assert_eq!(
+        vec!["READ_ONLY", "EXEC"],
+        (TestNames::READ_ONLY | TestNames::EXEC)
+            .iter_names()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn write() {
+    assert_eq!(
+        "read-only | exec | OTHER",
+        write(TestNames::READ_ONLY | TestNames::EXEC | TestNames::OTHER)
+    );
+    assert_eq!(
+        "mode=Fast | exec",
+        write(TestNames::EXEC | TestNames::from_bits_retain(1 << 3))
+    );
+    assert_eq!(
+        "TestNames(read-only)",
+        format!("{:?}", TestNames::READ_ONLY)
+    );
+
+    let mut s = String::new();
+    to_writer_strict(&TestNames::READ_ONLY, &mut s).unwrap();
+    assert_eq!("read-only", s);
+
+    // Source expressions always use the names of constants
+    let mut s = String::new();
+    to_writer_rust(&TestNames::READ_ONLY, &mut s, "TestNames").unwrap();
+    assert_eq!("TestNames::READ_ONLY", s);
+
+    fn write(value: TestNames) -> String {
+        let mut s = String::new();
+
+        to_writer(&value, &mut s).unwrap();
+        s
+    }
+}
+
+#[test]
+fn parse() {
+    for input in ["READ_ONLY", "read-only", "ro", "READONLY"] {
+        assert_eq!(
+            TestNames::READ_ONLY,
+            from_str::<TestNames>(input).unwrap(),
+            "{}",
+            input
+        );
+        assert_eq!(
+            TestNames::READ_ONLY,
+            from_str_strict::<TestNames>(input).unwrap(),
+            "{}",
+            input
+        );
+        assert_eq!(
+            TestNames::READ_ONLY,
+            from_bytes::<TestNames>(input.as_bytes()).unwrap(),
+            "{}",
+            input
+        );
+    }
+
+    assert_eq!(
+        TestNames::EXEC | TestNames::from_bits_retain(1 << 3),
+        from_str::<TestNames>("exec | mode=Fast").unwrap()
+    );
+    assert_eq!(
+        TestNames::EXEC | TestNames::from_bits_retain(1 << 3),
+        from_str::<TestNames>("EXEC | MODE=Fast").unwrap()
+    );
+
+    let options = ParserOptions::new().case_sensitive(false);
+    assert_eq!(
+        TestNames::READ_ONLY | TestNames::EXEC,
+        from_str_with::<TestNames>("Read-Only | RO | Exec", &options).unwrap()
+    );
+
+    let options = ParserOptions::new().deprecated_aliases(false);
+    assert_eq!(
+        TestNames::READ_ONLY,
+        from_str_with::<TestNames>("ro", &options).unwrap()
+    );
+    assert!(from_str_with::<TestNames>("READONLY", &options).is_err());
+
+    let err = from_str::<TestNames>("read-onyl").unwrap_err();
+    assert_eq!(Some("read-only"), err.suggestion());
+}
+
+#[test]
+fn valid_display_names() {
+    use crate::__private::is_valid_display_name;
+
+    for name in ["read-only", "read_only", "exec", "A1", "一", "ro.x"] {
+        assert!(is_valid_display_name(name), "{}", name);
+    }
+
+    for name in [
+        "",
+        "read only",
+        "a|b",
+        "a=b",
+        "1a",
+        "a\t",
+        "a\u{a0}b",
+        "a\u{3000}",
+    ] {
+        assert!(!is_valid_display_name(name), "{:?}", name);
+    }
+}
@@ -97,64 +97,870 @@ mod from_str {
             1 | 1 << 1,
             from_str::<TestUnicode>("一 | 二").unwrap().bits()
         );
+
+        assert_eq!(1 << 3, from_str::<TestFlags>("0b1000").unwrap().bits());
+        assert_eq!(1 << 3, from_str::<TestFlags>("0o10").unwrap().bits());
+        assert_eq!(1 << 3, from_str::<TestFlags>("8").unwrap().bits());
+        assert_eq!(0, from_str::<TestFlags>("0").unwrap().bits());
+        assert_eq!(
+            1 | 1 << 7,
+            from_str::<TestFlags>("A | 0b1000_0000").unwrap().bits()
+        );
+        assert_eq!(1 << 7, from_str::<TestFlags>("0x8_0").unwrap().bits());
+        assert_eq!(255, from_str::<TestFlags>("2_5_5").unwrap().bits());
+    }
+
+    #[test]
+    fn signs() {
+        bitflags! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            struct Signed: i8 {
+                const A = 1;
+            }
+        }
+
+        // Signs are accepted after the radix prefix, like `from_str_radix`
+        assert_eq!(1 << 3, from_str::<TestFlags>("0x+8").unwrap().bits());
+        assert_eq!(1 << 3, from_str::<TestFlags>("0b+1000").unwrap().bits());
+        assert_eq!(-1, from_str::<Signed>("0x-1").unwrap().bits());
+        assert_eq!(-128, from_str::<Signed>("0x-80").unwrap().bits());
+        assert_eq!(-128, from_str::<Signed>("0x80").unwrap().bits());
+        assert_eq!(1, from_str::<Signed>("0x+1").unwrap().bits());
+
+        assert!(from_str::<TestFlags>("0x-1").is_err());
+        assert!(from_str::<TestFlags>("0x+").is_err());
+        assert!(from_str::<TestFlags>("0x++1").is_err());
+        assert!(from_str::<Signed>("0x-81").is_err());
+        assert!(from_str::<Signed>("0x-+1").is_err());
+        assert!(from_str::<Signed>("0x--1").is_err());
+        assert!(from_str::<Signed>("0x-").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(from_str::<TestFlags>("a")
+            .unwrap_err()
+            .to_string()
+            .starts_with("unrecognized named flag"));
+        assert!(from_str::<TestFlags>("A & B")
+            .unwrap_err()
+            .to_string()
+            .starts_with("unrecognized named flag"));
+
+        assert!(from_str::<TestFlags>("0xg")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid hex flag"));
+        assert!(from_str::<TestFlags>("0xffffffffffff")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid hex flag"));
+        assert!(from_str::<TestFlags>("0x_")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid hex flag"));
+
+        assert!(from_str::<TestFlags>("0b2")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid number flag"));
+        assert!(from_str::<TestFlags>("0o8")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid number flag"));
+        assert!(from_str::<TestFlags>("256")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid number flag"));
+        assert!(from_str::<TestFlags>("1A")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid number flag"));
+        assert!(from_str::<TestFlags>("0b")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid number flag"));
+    }
+    #[test]
+    fn errors() {
+        let err = from_str::<TestFlags>("A | ABD").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(4..7), err.span());
+        assert_eq!(Some("ABC"), err.suggestion());
+
+        let err = from_str::<TestFlags>("abc").unwrap_err();
+        assert_eq!(Some(0..3), err.span());
+        assert_eq!(Some("ABC"), err.suggestion());
+
+        let err = from_str::<TestFlags>("A |  XYZW ").unwrap_err();
+        assert_eq!(Some(5..9), err.span());
+        assert_eq!(None, err.suggestion());
+
+        let err = from_str::<TestField>("MOD=Fast").unwrap_err();
+        assert_eq!(Some(0..8), err.span());
+        assert_eq!(Some("MODE"), err.suggestion());
+
+        let err = from_str::<TestFlags>("A | 0xg").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidHexFlag, err.kind());
+        assert_eq!(Some(4..7), err.span());
+        assert_eq!(None, err.suggestion());
+
+        let err = from_str::<TestFlags>("0b2").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumberFlag, err.kind());
+        assert_eq!(Some(0..3), err.span());
+
+        let err = from_str::<TestFlags>("A || B").unwrap_err();
+        assert_eq!(ParseErrorKind::EmptyFlag, err.kind());
+        assert_eq!(Some(3..3), err.span());
+
+        let err = from_str_strict::<TestFlags>("A | ABD").unwrap_err();
+        assert_eq!(Some(4..7), err.span());
+        assert_eq!(Some("ABC"), err.suggestion());
+
+        let err = ParseError::invalid_named_flag("ABD");
+        assert_eq!(None, err.span());
+        assert_eq!(None, err.suggestion());
+
+        #[cfg(feature = "alloc")]
+        {
+            assert_eq!(
+                "unrecognized named flag `ABD` at 4..7, did you mean `ABC`?",
+                from_str::<TestFlags>("A | ABD").unwrap_err().to_string()
+            );
+            assert_eq!(
+                "encountered empty flag at 3..3",
+                from_str::<TestFlags>("A || B").unwrap_err().to_string()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(bitflags_core_error)]
+    fn error() {
+        fn assert_error<E: core::error::Error>() {}
+
+        assert_error::<ParseError>();
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod from_str_collect_errors {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            1 | 1 << 1,
+            from_str_collect_errors::<TestFlags>("A | B", &ParserOptions::new())
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            0,
+            from_str_collect_errors::<TestFlags>("", &ParserOptions::new())
+                .unwrap()
+                .bits()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let errs = from_str_collect_errors::<TestFlags>("ABD | A | | 0xg", &ParserOptions::new())
+            .unwrap_err();
+
+        let errs = errs.errors();
+        assert_eq!(3, errs.len());
+
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, errs[0].kind());
+        assert_eq!(Some(0..3), errs[0].span());
+        assert_eq!(Some("ABC"), errs[0].suggestion());
+
+        assert_eq!(ParseErrorKind::EmptyFlag, errs[1].kind());
+        assert_eq!(Some(9..9), errs[1].span());
+
+        assert_eq!(ParseErrorKind::InvalidHexFlag, errs[2].kind());
+        assert_eq!(Some(12..15), errs[2].span());
+
+        let errs = from_str_collect_errors::<TestFlags>(
+            "ABD XYZW",
+            &ParserOptions::new().whitespace_separators(true),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            "unrecognized named flag `ABD` at 0..3, did you mean `ABC`?; unrecognized named flag `XYZW` at 4..8",
+            errs.to_string()
+        );
+        assert_eq!(2, errs.into_iter().count());
+    }
+}
+
+mod to_writer {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        assert_eq!("", write(TestFlags::empty()));
+        assert_eq!("A", write(TestFlags::A));
+        assert_eq!("A | B | C", write(TestFlags::all()));
+        assert_eq!("0x8", write(TestFlags::from_bits_retain(1 << 3)));
+        assert_eq!(
+            "A | 0x8",
+            write(TestFlags::A | TestFlags::from_bits_retain(1 << 3))
+        );
+
+        assert_eq!("", write(TestZero::ZERO));
+
+        assert_eq!("ABC", write(TestFlagsInvert::all()));
+
+        assert_eq!("0x1", write(TestOverlapping::from_bits_retain(1)));
+
+        assert_eq!("A", write(TestOverlappingFull::C));
+        assert_eq!(
+            "A | D",
+            write(TestOverlappingFull::C | TestOverlappingFull::D)
+        );
+    }
+
+    fn write<F: Flags>(value: F) -> String
+    where
+        F::Bits: crate::parser::WriteHex,
+    {
+        let mut s = String::new();
+
+        to_writer(&value, &mut s).unwrap();
+        s
+    }
+}
+
+mod from_str_with {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let default = ParserOptions::new();
+
+        assert_eq!(
+            1 | 1 << 1,
+            from_str_with::<TestFlags>("A | B", &default)
+                .unwrap()
+                .bits()
+        );
+
+        let case_insensitive = ParserOptions::new().case_sensitive(false);
+
+        assert_eq!(
+            1 | 1 << 1 | 1 << 3,
+            from_str_with::<TestFlags>("a | B | 0X8", &case_insensitive)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 2,
+            from_str_with::<TestField>("mode=turbo", &case_insensitive)
+                .unwrap()
+                .bits()
+        );
+
+        let separators = ParserOptions::new().separators(&[',', '+']);
+
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_str_with::<TestFlags>("A,B + C", &separators)
+                .unwrap()
+                .bits()
+        );
+
+        // Options may borrow data that's only known at runtime, like configuration
+        let configured = String::from(";/").chars().collect::<Vec<_>>();
+        let name = format!("Test{}", "Flags");
+        let runtime = ParserOptions::new()
+            .separators(&configured)
+            .type_name(&name);
+
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_str_with::<TestFlags>("TestFlags::A; B / C", &runtime)
+                .unwrap()
+                .bits()
+        );
+
+        let whitespace = ParserOptions::new().whitespace_separators(true);
+
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_str_with::<TestFlags>(" A  B\t| C ", &whitespace)
+                .unwrap()
+                .bits()
+        );
+
+        let trailing = ParserOptions::new()
+            .separators(&[','])
+            .trailing_separator(true);
+
+        assert_eq!(
+            1 | 1 << 1,
+            from_str_with::<TestFlags>("A, B,", &trailing)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1,
+            from_str_with::<TestFlags>("A , ", &trailing)
+                .unwrap()
+                .bits()
+        );
+    }
+
+    #[test]
+    fn type_name() {
+        let options = ParserOptions::new().type_name("TestFlags");
+
+        for value in [
+            TestFlags::empty(),
+            TestFlags::A,
+            TestFlags::all(),
+            TestFlags::A | TestFlags::from_bits_retain(0x40),
+        ] {
+            let debug = format!("{:?}", value);
+            assert_eq!(value, from_str_with(&debug, &options).unwrap(), "{}", debug);
+
+            let debug = format!("{:#?}", value);
+            assert_eq!(value, from_str_with(&debug, &options).unwrap(), "{}", debug);
+
+            let mut rust = String::new();
+            to_writer_rust(&value, &mut rust, "crate::TestFlags").unwrap();
+            assert_eq!(value, from_str_with(&rust, &options).unwrap(), "{}", rust);
+        }
+
+        assert_eq!(
+            TestFlags::A | TestFlags::B,
+            from_str_with("TestFlags::A | B", &options).unwrap()
+        );
+        assert_eq!(
+            TestFlags::empty(),
+            from_str_with("TestFlags()", &options).unwrap()
+        );
+        assert_eq!(
+            TestField::A | TestField::from_bits_retain(1 << 1),
+            from_str_with(
+                "TestField(TestField::MODE=Fast | A)",
+                &ParserOptions::new().type_name("TestField")
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            TestFlags::A,
+            from_str_with(
+                "testflags::a",
+                &ParserOptions::new()
+                    .type_name("TestFlags")
+                    .case_sensitive(false)
+            )
+            .unwrap()
+        );
+
+        let err = from_str_with::<TestFlags>("TestFlags(A | OtherFlags::B)", &options).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(14..27), err.span());
+
+        let err =
+            from_str_with::<TestFlags>("TestFlags::from_bits_retain(A)", &options).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumberFlag, err.kind());
+        assert_eq!(Some(28..29), err.span());
+
+        // The type name isn't accepted unless it's given in options
+        assert!(from_str::<TestFlags>("TestFlags(A)").is_err());
+        assert!(from_str::<TestFlags>("TestFlags::A").is_err());
+    }
+
+    #[test]
+    fn expressions() {
+        let options = ParserOptions::new().expressions(true);
+
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_str_with::<TestFlags>("all", &options).unwrap().bits()
+        );
+        assert_eq!(
+            0,
+            from_str_with::<TestFlags>("empty", &options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 3,
+            from_str_with::<TestFlags>("none | 0x8", &options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 2,
+            from_str_with::<TestFlags>("all - B", &options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 2,
+            from_str_with::<TestFlags>("all-A-B", &options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 1 | 1 << 2,
+            from_str_with::<TestFlags>("!A", &options).unwrap().bits()
+        );
+        assert_eq!(
+            1 | 1 << 7,
+            from_str_with::<TestFlags>("! ! A | 0x80", &options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 1,
+            from_str_with::<TestFlags>("ABC - A - C", &options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 2 | 1 << 3,
+            from_str_with::<TestField>("all - A - MODE=Fast", &options)
+                .unwrap()
+                .bits()
+        );
+
+        let whitespace = options.whitespace_separators(true);
+
+        assert_eq!(
+            1 | 1 << 2,
+            from_str_with::<TestFlags>("all - B", &whitespace)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 2 | 1 << 3,
+            from_str_with::<TestFlags>("all -A  -B 0x8", &whitespace)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 2,
+            from_str_with::<TestFlags>("A ! A - B", &whitespace)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 7,
+            from_str_with::<TestFlags>("! ! A 0x80", &whitespace)
+                .unwrap()
+                .bits()
+        );
+
+        let err = from_str_with::<TestFlags>("A all -", &whitespace).unwrap_err();
+        assert_eq!(ParseErrorKind::EmptyFlag, err.kind());
+
+        let case_insensitive = options.case_sensitive(false);
+
+        assert_eq!(
+            1 | 1 << 2,
+            from_str_with::<TestFlags>("ALL - b", &case_insensitive)
+                .unwrap()
+                .bits()
+        );
+
+        // Names containing `-` are preferred over splitting them
+        assert_eq!(
+            TestNames::all() - TestNames::READ_ONLY - TestNames::EXEC,
+            from_str_with::<TestNames>("all - read-only - exec", &options).unwrap()
+        );
+        assert_eq!(
+            TestNames::all() - TestNames::READ_ONLY,
+            from_str_with::<TestNames>("!read-only", &options).unwrap()
+        );
+        assert_eq!(
+            TestNames::READ_ONLY | TestNames::EXEC,
+            from_str_with::<TestNames>("read-only exec", &whitespace).unwrap()
+        );
+
+        let debug = format!("{:?}", TestNames::READ_ONLY | TestNames::EXEC);
+        assert_eq!(
+            TestNames::READ_ONLY | TestNames::EXEC,
+            from_str_with::<TestNames>(&debug, &options.type_name("TestNames")).unwrap()
+        );
+
+        assert!(from_str_with::<TestFlags>("all -", &options).is_err());
+        assert!(from_str_with::<TestFlags>("!", &options).is_err());
+        assert!(from_str_with::<TestFlags>("- A", &options).is_err());
+        assert!(from_str_with::<TestFlags>("all", &ParserOptions::new()).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let default = ParserOptions::new();
+
+        assert!(from_str_with::<TestFlags>("a", &default).is_err());
+        assert!(from_str_with::<TestFlags>("A, B", &default).is_err());
+        assert!(from_str_with::<TestFlags>("A |", &default).is_err());
+        assert!(from_str_with::<TestFlags>("0X8", &default).is_err());
+
+        let trailing = ParserOptions::new().trailing_separator(true);
+
+        assert!(from_str_with::<TestFlags>("A || B", &trailing).is_err());
+        assert!(from_str_with::<TestFlags>("| A", &trailing).is_err());
+
+        let separators = ParserOptions::new().separators(&[',']);
+
+        assert!(from_str_with::<TestFlags>("A | B", &separators).is_err());
+        assert!(from_str_with::<TestFlags>("A B", &separators).is_err());
+    }
+}
+
+mod to_writer_with {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        let options = ParserOptions::new().write_separator(", ");
+
+        assert_eq!("", write(TestFlags::empty(), &options));
+        assert_eq!("A", write(TestFlags::A, &options));
+        assert_eq!("A, B, C", write(TestFlags::all(), &options));
+        assert_eq!(
+            "A, 0x8",
+            write(TestFlags::A | TestFlags::from_bits_retain(1 << 3), &options)
+        );
+        assert_eq!(
+            "MODE=Fast, A",
+            write(TestField::A | TestField::from_bits_retain(1 << 1), &options)
+        );
+
+        assert_eq!(
+            "A | B | C",
+            write(TestFlags::all(), &ParserOptions::default())
+        );
+    }
+
+    #[test]
+    fn radix() {
+        let unknown = TestFlags::A | TestFlags::from_bits_retain(0b1010_0000);
+
+        for (expected, radix) in [
+            ("A | 0b10100000", Radix::Binary),
+            ("A | 0o240", Radix::Octal),
+            ("A | 160", Radix::Decimal),
+            ("A | 0xa0", Radix::Hex),
+        ] {
+            let options = ParserOptions::new().write_radix(radix);
+
+            assert_eq!(expected, write(unknown, &options));
+            assert_eq!(unknown, from_str_with(expected, &options).unwrap());
+        }
+    }
+
+    #[test]
+    fn canonical() {
+        let options = ParserOptions::new().canonical(true);
+
+        assert_eq!("", write(TestFlags::empty(), &options));
+        assert_eq!("A", write(TestFlags::A, &options));
+        assert_eq!("A | B", write(TestFlags::A | TestFlags::B, &options));
+        assert_eq!("ABC", write(TestFlags::all(), &options));
+        assert_eq!(
+            "ABC | 0x8",
+            write(
+                TestFlags::all() | TestFlags::from_bits_retain(1 << 3),
+                &options
+            )
+        );
+        assert_eq!("ABC", write(TestFlagsInvert::all(), &options));
+        assert_eq!(
+            "A | C",
+            write(TestFlagsInvert::A | TestFlagsInvert::C, &options)
+        );
+        assert_eq!("AB | BC", write(TestOverlapping::all(), &options));
+        assert_eq!(
+            "AB",
+            write(TestOverlapping::from_bits_retain(0b011), &options)
+        );
+        assert_eq!(
+            "0x5",
+            write(TestOverlapping::from_bits_retain(0b101), &options)
+        );
+        assert_eq!("A | D", write(TestOverlappingFull::all(), &options));
+        assert_eq!(
+            "MODE=Fast | A",
+            write(TestField::A | TestField::from_bits_retain(1 << 1), &options)
+        );
+
+        assert_eq!(
+            TestFlags::all(),
+            from_str_with::<TestFlags>(&write(TestFlags::all(), &options), &options).unwrap()
+        );
+    }
+
+    #[test]
+    fn canonical_many_flags() {
+        bitflags! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            struct Large: u64 {
+                const B0 = 1 << 0;
+                const B1 = 1 << 1;
+                const B2 = 1 << 2;
+                const B3 = 1 << 3;
+                const B4 = 1 << 4;
+                const B5 = 1 << 5;
+                const B6 = 1 << 6;
+                const B7 = 1 << 7;
+                const B8 = 1 << 8;
+                const B9 = 1 << 9;
+                const B10 = 1 << 10;
+                const B11 = 1 << 11;
+                const B12 = 1 << 12;
+                const B13 = 1 << 13;
+                const B14 = 1 << 14;
+                const B15 = 1 << 15;
+                const B16 = 1 << 16;
+                const B17 = 1 << 17;
+                const B18 = 1 << 18;
+                const B19 = 1 << 19;
+                const B20 = 1 << 20;
+                const B21 = 1 << 21;
+                const B22 = 1 << 22;
+                const B23 = 1 << 23;
+                const B24 = 1 << 24;
+                const B25 = 1 << 25;
+                const B26 = 1 << 26;
+                const B27 = 1 << 27;
+                const B28 = 1 << 28;
+                const B29 = 1 << 29;
+                const B30 = 1 << 30;
+                const B31 = 1 << 31;
+                const B32 = 1 << 32;
+                const B33 = 1 << 33;
+                const B34 = 1 << 34;
+                const B35 = 1 << 35;
+                const B36 = 1 << 36;
+                const B37 = 1 << 37;
+                const B38 = 1 << 38;
+                const B39 = 1 << 39;
+                const B40 = 1 << 40;
+                const B41 = 1 << 41;
+                const B42 = 1 << 42;
+                const B43 = 1 << 43;
+                const B44 = 1 << 44;
+                const B45 = 1 << 45;
+                const B46 = 1 << 46;
+                const B47 = 1 << 47;
+                const B48 = 1 << 48;
+                const B49 = 1 << 49;
+                const B50 = 1 << 50;
+                const B51 = 1 << 51;
+                const B52 = 1 << 52;
+                const B53 = 1 << 53;
+                const B54 = 1 << 54;
+                const B55 = 1 << 55;
+                const B56 = 1 << 56;
+                const B57 = 1 << 57;
+                const B58 = 1 << 58;
+                const B59 = 1 << 59;
+                const B60 = 1 << 60;
+                const B61 = 1 << 61;
+                const B62 = 1 << 62;
+                const B63 = 1 << 63;
+
+                const P0 = 0b11;
+                const P1 = 0b11 << 2;
+                const P2 = 0b11 << 4;
+                const P3 = 0b11 << 6;
+                const P4 = 0b11 << 8;
+                const P5 = 0b11 << 10;
+                const P6 = 0b11 << 12;
+                const P7 = 0b11 << 14;
+                const P8 = 0b11 << 16;
+                const P9 = 0b11 << 18;
+                const P10 = 0b11 << 20;
+                const P11 = 0b11 << 22;
+                const P12 = 0b11 << 24;
+                const P13 = 0b11 << 26;
+                const P14 = 0b11 << 28;
+                const P15 = 0b11 << 30;
+                const P16 = 0b11 << 32;
+                const P17 = 0b11 << 34;
+                const P18 = 0b11 << 36;
+                const P19 = 0b11 << 38;
+                const P20 = 0b11 << 40;
+                const P21 = 0b11 << 42;
+                const P22 = 0b11 << 44;
+                const P23 = 0b11 << 46;
+                const P24 = 0b11 << 48;
+                const P25 = 0b11 << 50;
+                const P26 = 0b11 << 52;
+                const P27 = 0b11 << 54;
+                const P28 = 0b11 << 56;
+                const P29 = 0b11 << 58;
+                const P30 = 0b11 << 60;
+                const P31 = 0b11 << 62;
+
+                const LOW = 0xffff_ffff;
+            }
+        }
+
+        let options = ParserOptions::new().canonical(true);
+
+        // Few enough flags are contained in these values to find the smallest set of names
+        assert_eq!(
+            "P0 | P1",
+            write(Large::B0 | Large::B1 | Large::P1, &options)
+        );
+        assert_eq!(
+            "B0 | B63 | P1",
+            write(Large::B0 | Large::P1 | Large::B63, &options)
+        );
+
+        // Too many flags are contained in these values to search every set of names,
+        // so only the flags that aren't contained in any others are written
+        let expected = (16..32)
+            .map(|i| format!("P{}", i))
+            .chain(Some(String::from("LOW")))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        assert_eq!(expected, write(Large::all(), &options));
+        assert_eq!(Large::all(), from_str(&expected).unwrap());
+
+        let expected = (16..32)
+            .map(|i| format!("P{}", i))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        assert_eq!(
+            expected,
+            write(Large::from_bits_retain(0xffff_ffff_0000_0000), &options)
+        );
     }
 
     #[test]
-    fn invalid() {
-        assert!(from_str::<TestFlags>("a")
-            .unwrap_err()
-            .to_string()
-            .starts_with("unrecognized named flag"));
-        assert!(from_str::<TestFlags>("A & B")
-            .unwrap_err()
-            .to_string()
-            .starts_with("unrecognized named flag"));
+    fn canonical_many_overlapping_flags() {
+        bitflags! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            struct Chain: u32 {
+                const C0 = 0b11;
+                const C1 = 0b11 << 1;
+                const C2 = 0b11 << 2;
+                const C3 = 0b11 << 3;
+                const C4 = 0b11 << 4;
+                const C5 = 0b11 << 5;
+                const C6 = 0b11 << 6;
+                const C7 = 0b11 << 7;
+                const C8 = 0b11 << 8;
+                const C9 = 0b11 << 9;
+                const C10 = 0b11 << 10;
+                const C11 = 0b11 << 11;
+                const C12 = 0b11 << 12;
+                const C13 = 0b11 << 13;
+                const C14 = 0b11 << 14;
+                const C15 = 0b11 << 15;
+                const C16 = 0b11 << 16;
+                const C17 = 0b11 << 17;
+            }
+        }
 
-        assert!(from_str::<TestFlags>("0xg")
-            .unwrap_err()
-            .to_string()
-            .starts_with("invalid hex flag"));
-        assert!(from_str::<TestFlags>("0xffffffffffff")
-            .unwrap_err()
-            .to_string()
-            .starts_with("invalid hex flag"));
+        let options = ParserOptions::new().canonical(true);
+
+        // Too many flags are contained in this value to search every set of names, but any
+        // name whose bits are covered by the others is still skipped
+        let expected = "C0 | C2 | C4 | C6 | C8 | C10 | C12 | C14 | C16 | C17";
+
+        assert_eq!(expected, write(Chain::all(), &options));
+        assert_eq!(Chain::all(), from_str(expected).unwrap());
+
+        // Few enough flags are contained in this value to find the smallest set of names
+        assert_eq!(
+            "C0 | C1 | C3",
+            write(Chain::C0 | Chain::C1 | Chain::C2 | Chain::C3, &options)
+        );
+    }
+
+    fn write<F: Flags>(value: F, options: &ParserOptions<'_>) -> String
+    where
+        F::Bits: crate::parser::WriteHex,
+    {
+        let mut s = String::new();
+
+        to_writer_with(&value, &mut s, options).unwrap();
+        s
     }
 }
 
-mod to_writer {
+mod to_writer_rust {
     use super::*;
 
     #[test]
     fn cases() {
-        assert_eq!("", write(TestFlags::empty()));
-        assert_eq!("A", write(TestFlags::A));
-        assert_eq!("A | B | C", write(TestFlags::all()));
-        assert_eq!("0x8", write(TestFlags::from_bits_retain(1 << 3)));
+        assert_eq!("TestFlags::empty()", write(TestFlags::empty(), "TestFlags"));
+        assert_eq!("TestFlags::A", write(TestFlags::A, "TestFlags"));
         assert_eq!(
-            "A | 0x8",
-            write(TestFlags::A | TestFlags::from_bits_retain(1 << 3))
+            "crate::TestFlags::A | crate::TestFlags::B | crate::TestFlags::C",
+            write(TestFlags::all(), "crate::TestFlags")
+        );
+        assert_eq!(
+            "TestFlags::A | TestFlags::from_bits_retain(0x40)",
+            write(
+                TestFlags::A | TestFlags::from_bits_retain(0x40),
+                "TestFlags"
+            )
+        );
+        assert_eq!(
+            "TestFlags::from_bits_retain(0x40)",
+            write(TestFlags::from_bits_retain(0x40), "TestFlags")
+        );
+        assert_eq!(
+            "TestField::A | TestField::MODE",
+            write(TestField::A | TestField::MODE, "TestField")
+        );
+        assert_eq!(
+            "TestField::from_bits_retain(0x2)",
+            write(TestField::from_bits_retain(0b010), "TestField")
+        );
+        assert_eq!(
+            "TestReserved::A",
+            write(TestReserved::from_bits_retain(0b1100_0001), "TestReserved")
         );
+    }
 
-        assert_eq!("", write(TestZero::ZERO));
+    fn write<F: Flags>(value: F, path: &str) -> String
+    where
+        F::Bits: crate::parser::WriteHex,
+    {
+        let mut s = String::new();
 
-        assert_eq!("ABC", write(TestFlagsInvert::all()));
+        to_writer_rust(&value, &mut s, path).unwrap();
+        s
+    }
+}
 
-        assert_eq!("0x1", write(TestOverlapping::from_bits_retain(1)));
+mod to_writer_c {
+    use super::*;
 
-        assert_eq!("A", write(TestOverlappingFull::C));
+    #[test]
+    fn cases() {
+        assert_eq!("0", write(TestFlags::empty(), "FLAG_"));
+        assert_eq!("FLAG_A", write(TestFlags::A, "FLAG_"));
+        assert_eq!("A | B | C", write(TestFlags::all(), ""));
         assert_eq!(
-            "A | D",
-            write(TestOverlappingFull::C | TestOverlappingFull::D)
+            "FLAG_A | 0x40",
+            write(TestFlags::A | TestFlags::from_bits_retain(0x40), "FLAG_")
         );
+        assert_eq!("0x40", write(TestFlags::from_bits_retain(0x40), "FLAG_"));
     }
 
-    fn write<F: Flags>(value: F) -> String
+    fn write<F: Flags>(value: F, prefix: &str) -> String
     where
         F::Bits: crate::parser::WriteHex,
     {
         let mut s = String::new();
 
-        to_writer(&value, &mut s).unwrap();
+        to_writer_c(&value, &mut s, prefix).unwrap();
         s
     }
 }
@@ -293,6 +1099,322 @@ mod from_str_strict {
     }
 }
 
+mod from_names {
+    use super::*;
+
+    #[test]
+    fn flags_method() {
+        assert_eq!(
+            TestFlags::A | TestFlags::B,
+            <TestFlags as Flags>::from_names(["A", "B"]).unwrap()
+        );
+        assert_eq!(
+            TestField::A | TestField::from_bits_retain(1 << 1 | 1 << 3),
+            <TestField as Flags>::from_names(vec!["A", "MODE=Fast", "0x8"]).unwrap()
+        );
+
+        let err = <TestFlags as Flags>::from_names(["A", "ABD"]).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(1), err.index());
+    }
+
+    #[test]
+    fn valid() {
+        let empty: [&str; 0] = [];
+        assert_eq!(0, from_names::<TestFlags, _>(empty).unwrap().bits());
+
+        assert_eq!(1, from_names::<TestFlags, _>(["A"]).unwrap().bits());
+        assert_eq!(1, from_names::<TestFlags, _>([" A "]).unwrap().bits());
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_names::<TestFlags, _>(["A", "B", "C"]).unwrap().bits()
+        );
+        assert_eq!(
+            1 | 1 << 5,
+            from_names::<TestFlags, _>(vec![String::from("A"), String::from("0x20")])
+                .unwrap()
+                .bits()
+        );
+
+        assert_eq!(
+            1 | 1 << 1,
+            from_names::<TestField, _>(["A", "MODE=Fast"])
+                .unwrap()
+                .bits()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = from_names::<TestFlags, _>(["A", "ABD"]).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(0..3), err.span());
+        assert_eq!(Some(1), err.index());
+        assert_eq!(Some("ABC"), err.suggestion());
+
+        let err = from_names::<TestFlags, _>(["A | B"]).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(0), err.index());
+
+        let err = from_names::<TestFlags, _>(["A", "B", " "]).unwrap_err();
+        assert_eq!(ParseErrorKind::EmptyFlag, err.kind());
+        assert_eq!(Some(0..0), err.span());
+        assert_eq!(Some(2), err.index());
+
+        let err = from_names::<TestFlags, _>(["0xg"]).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidHexFlag, err.kind());
+
+        assert_eq!(None, from_str::<TestFlags>("ABD").unwrap_err().index());
+
+        #[cfg(feature = "alloc")]
+        {
+            assert_eq!(
+                "unrecognized named flag `ABD` at 0..3 in item 1, did you mean `ABC`?",
+                err_string(["A", "ABD"])
+            );
+
+            fn err_string<const N: usize>(names: [&str; N]) -> String {
+                from_names::<TestFlags, _>(names).unwrap_err().to_string()
+            }
+        }
+    }
+}
+
+mod from_names_truncate {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            1,
+            from_names_truncate::<TestFlags, _>(["A", "0x20"])
+                .unwrap()
+                .bits()
+        );
+    }
+}
+
+mod from_names_strict {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            1 | 1 << 1,
+            from_names_strict::<TestFlags, _>(["A", "B"])
+                .unwrap()
+                .bits()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = from_names_strict::<TestFlags, _>(["A", "0x1"]).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumberFlag, err.kind());
+        assert_eq!(Some(1), err.index());
+
+        let err = from_names_strict::<TestFlags, _>(["a"]).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+    }
+}
+
+mod from_bytes {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(0, from_bytes::<TestFlags>(b"").unwrap().bits());
+        assert_eq!(0, from_bytes::<TestFlags>(b" \t ").unwrap().bits());
+
+        assert_eq!(1, from_bytes::<TestFlags>(b"A").unwrap().bits());
+        assert_eq!(1, from_bytes::<TestFlags>(b" A ").unwrap().bits());
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_bytes::<TestFlags>(b"A\n|\tB\r\n|   C ")
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 3,
+            from_bytes::<TestFlags>(b"A | 0x8").unwrap().bits()
+        );
+        assert_eq!(1 << 3, from_bytes::<TestFlags>(b"0b1000").unwrap().bits());
+        assert_eq!(1 << 3, from_bytes::<TestFlags>(b"0o10").unwrap().bits());
+        assert_eq!(255, from_bytes::<TestFlags>(b"2_5_5").unwrap().bits());
+
+        assert_eq!(
+            1 | 1 << 1,
+            from_bytes::<TestUnicode>("一 | 二".as_bytes())
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            TestField::A | TestField::from_bits_retain(1 << 1),
+            from_bytes::<TestField>(b"MODE = Fast | A").unwrap()
+        );
+        assert_eq!(
+            0b0110_0001,
+            from_bytes::<TestReserved>(b"A | 0xa0").unwrap().bits()
+        );
+
+        for input in ["A | B | 0xf0", "MODE=Turbo | B", "", "0b1_1"] {
+            assert_eq!(
+                from_str::<TestField>(input).unwrap(),
+                from_bytes::<TestField>(input.as_bytes()).unwrap(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let err = from_bytes::<TestFlags>(b"A | ABD").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(4..7), err.span());
+        assert_eq!(Some("ABC"), err.suggestion());
+
+        let err = from_bytes::<TestFlags>(b"A | \xff").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(4..5), err.span());
+
+        let err = from_bytes::<TestField>(b"MODE=\xff").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(0..6), err.span());
+
+        let err = from_bytes::<TestFlags>(b"A || B").unwrap_err();
+        assert_eq!(ParseErrorKind::EmptyFlag, err.kind());
+
+        let err = from_bytes::<TestFlags>(b"0xg").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidHexFlag, err.kind());
+        assert_eq!(Some(0..3), err.span());
+
+        let err = from_bytes::<TestFlags>(b"0x1ff").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidHexFlag, err.kind());
+
+        let err = from_bytes::<TestFlags>(b"0b2").unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNumberFlag, err.kind());
+
+        #[cfg(feature = "alloc")]
+        {
+            assert_eq!(
+                "unrecognized named flag `A\\xffB` at 0..3",
+                from_bytes::<TestFlags>(b"A\xffB").unwrap_err().to_string()
+            );
+        }
+    }
+}
+
+mod from_bytes_with {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let options = ParserOptions::new()
+            .separators(&['|', ','])
+            .case_sensitive(false)
+            .whitespace_separators(true)
+            .trailing_separator(true)
+            .expressions(true)
+            .type_name("TestField");
+
+        assert_eq!(
+            TestField::A | TestField::from_bits_retain(1 << 1),
+            from_bytes_with::<TestField>(b"a, mode=fast,", &options).unwrap()
+        );
+        assert_eq!(
+            TestField::A,
+            from_bytes_with::<TestField>(b"TestField(all - MODE - B)", &options).unwrap()
+        );
+        assert_eq!(
+            TestField::A | TestField::B,
+            from_bytes_with::<TestField>(b"TestField::A 0X8", &options).unwrap()
+        );
+
+        for input in [
+            "A B, 0x8 |",
+            "all - A",
+            "! A",
+            "mode=turbo",
+            "TestField(A | MODE=Fast,)",
+            "crate::TestField::from_bits_retain(0b1)",
+        ] {
+            assert_eq!(
+                from_str_with::<TestField>(input, &options).unwrap(),
+                from_bytes_with::<TestField>(input.as_bytes(), &options).unwrap(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let options = ParserOptions::new().separators(&[',']);
+
+        let err = from_bytes_with::<TestFlags>(b"A | B", &options).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(0..5), err.span());
+
+        let err = from_bytes_with::<TestFlags>(b"A, \xff", &options).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some(3..4), err.span());
+
+        let err = from_bytes_with::<TestFlags>(b"a", &ParserOptions::new()).unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidNamedFlag, err.kind());
+        assert_eq!(Some("A"), err.suggestion());
+    }
+}
+
+mod from_bytes_truncate {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            1,
+            from_bytes_truncate::<TestFlags>(b"A | 0x8").unwrap().bits()
+        );
+        assert_eq!(
+            1 | 1 << 1,
+            from_bytes_truncate::<TestFlags>(b"A | B").unwrap().bits()
+        );
+    }
+}
+
+mod from_bytes_strict {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(0, from_bytes_strict::<TestFlags>(b"").unwrap().bits());
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_bytes_strict::<TestFlags>(b"A|B|C").unwrap().bits()
+        );
+        assert_eq!(
+            1 << 1,
+            from_bytes_strict::<TestField>(b"MODE=Fast").unwrap().bits()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            ParseErrorKind::InvalidHexFlag,
+            from_bytes_strict::<TestFlags>(b"0x1").unwrap_err().kind()
+        );
+        assert_eq!(
+            ParseErrorKind::InvalidNumberFlag,
+            from_bytes_strict::<TestFlags>(b"1").unwrap_err().kind()
+        );
+        assert_eq!(
+            ParseErrorKind::InvalidNamedFlag,
+            from_bytes_strict::<TestFlags>(b"a").unwrap_err().kind()
+        );
+    }
+}
+
 mod to_writer_strict {
     use super::*;
 
@@ -0,0 +1,138 @@
+use super::*;
+
+use crate::{parser::*, Flags};
+
+#[test]
+fn cases() {
+    assert_eq!(1 << 7, TestReserved::RESERVED_ZERO);
+    assert_eq!(1 << 6, TestReserved::RESERVED_ONE);
+
+    assert_eq!(0, TestFlags::RESERVED_ZERO);
+    assert_eq!(0, TestFlags::RESERVED_ONE);
+}
+
+#[test]
+fn empty() {
+    // Empty values unset all bits, even reserved ones that must be set
+    assert_eq!(0, TestReserved::empty().bits());
+    assert_eq!(0, <TestReserved as Flags>::empty().bits());
+    assert!(TestReserved::empty().is_empty());
+    assert_eq!(None, TestReserved::from_bits(TestReserved::empty().bits()));
+
+    assert_eq!(0b0100_0000, TestReserved::from_bits_truncate(0).bits());
+}
+
+#[test]
+fn default() {
+    // Default values set reserved bits that must be set, so they're valid
+    assert_eq!(0b0100_0000, TestReserved::default().bits());
+    assert_eq!(
+        Some(TestReserved::default()),
+        TestReserved::from_bits(TestReserved::default().bits())
+    );
+    assert_eq!(TestReserved::from_bits_truncate(0), TestReserved::default());
+}
+
+#[test]
+fn all() {
+    assert_eq!(0b0111_1111, TestReserved::all().bits());
+    assert_eq!(0b0111_1111, <TestReserved as Flags>::all().bits());
+
+    assert!(TestReserved::from_bits_retain(0b0111_1111).is_all());
+    assert!(!TestReserved::from_bits_retain(0b0011_1111).is_all());
+}
+
+#[test]
+fn from_bits() {
+    case(None, 0b0000_0001);
+    case(None, 0b1100_0001);
+    case(Some(0b0100_0001), 0b0100_0001);
+    case(Some(0b0111_1111), 0b0111_1111);
+
+    #[track_caller]
+    fn case(expected: Option<u8>, input: u8) {
+        assert_eq!(
+            expected,
+            TestReserved::from_bits(input).map(|f| f.bits()),
+            "TestReserved::from_bits({})",
+            input
+        );
+        assert_eq!(
+            expected,
+            <TestReserved as Flags>::from_bits(input).map(|f| f.bits()),
+            "Flags::from_bits({})",
+            input
+        );
+    }
+}
+
+#[test]
+fn from_bits_truncate() {
+    case(0b0100_0000, 0);
+    case(0b0100_0001, 0b1000_0001);
+    case(0b0111_1111, !0);
+
+    #[track_caller]
+    fn case(expected: u8, input: u8) {
+        assert_eq!(
+            expected,
+            TestReserved::from_bits_truncate(input).bits(),
+            "TestReserved::from_bits_truncate({})",
+            input
+        );
+        assert_eq!(
+            expected,
+            <TestReserved as Flags>::from_bits_truncate(input).bits(),
+            "Flags::from_bits_truncate({})",
+            input
+        );
+    }
+}
+
+#[test]
+fn complement() {
+    assert_eq!(
+        0b0111_1110,
+        TestReserved::from_bits_retain(0b0100_0001)
+            .complement()
+            .bits()
+    );
+    assert_eq!(
+        0b0111_1110,
+        Flags::complement(TestReserved::from_bits_retain(0b0100_0001)).bits()
+    );
+    assert_eq!(0b0111_1111, (!TestReserved::empty()).bits());
+    assert_eq!(0b0100_0000, (!TestReserved::all()).bits());
+}
+
+#[test]
+fn parser() {
+    assert_eq!("A", write(TestReserved::from_bits_retain(0b0100_0001)));
+    assert_eq!("A", write(TestReserved::from_bits_retain(0b1000_0001)));
+    assert_eq!(
+        "A | 0x20",
+        write(TestReserved::from_bits_retain(0b1110_0001))
+    );
+    assert_eq!(
+        "0x0",
+        format!("{:?}", TestReserved::from_bits_retain(1 << 6).0)
+    );
+
+    assert_eq!(0b0100_0001, from_str::<TestReserved>("A").unwrap().bits());
+    assert_eq!(0b0100_0000, from_str::<TestReserved>("").unwrap().bits());
+    assert_eq!(
+        0b0110_0001,
+        from_str::<TestReserved>("A | 0xa0").unwrap().bits()
+    );
+    assert_eq!(
+        0b0100_0010,
+        from_str_strict::<TestReserved>("B").unwrap().bits()
+    );
+
+    fn write(value: TestReserved) -> String {
+        let mut s = String::new();
+
+        to_writer(&value, &mut s).unwrap();
+        s
+    }
+}
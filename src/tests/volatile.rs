@@ -0,0 +1,73 @@
+use crate::volatile::VolatileFlags;
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(transparent)]
+    struct Register: u8 {
+        const ENABLE = 1;
+        const MODE = 1 << 1;
+        const IRQ_PENDING = 1 << 2;
+        const START = 1 << 3;
+    }
+}
+
+#[test]
+fn read_write() {
+    let mut bits = 0b0101;
+    let mut register = VolatileFlags::<Register>::from_mut(&mut bits);
+
+    assert_eq!(Register::ENABLE | Register::IRQ_PENDING, register.read());
+
+    register.write(Register::MODE);
+    assert_eq!(Register::MODE, register.read());
+
+    register.insert(Register::ENABLE);
+    register.remove(Register::MODE);
+    assert_eq!(Register::ENABLE, register.read());
+}
+
+#[test]
+fn write_one_to_clear() {
+    let mut bits = 0b0101;
+    VolatileFlags::<Register>::from_mut(&mut bits)
+        .write_one_to_clear(Register::IRQ_PENDING)
+        .modify(|r| r.insert(Register::MODE));
+
+    // Leaving the pending bit set writes a `0` to it
+    assert_eq!(0b0011, bits);
+
+    let mut bits = 0b0101;
+    VolatileFlags::<Register>::from_mut(&mut bits)
+        .write_one_to_clear(Register::IRQ_PENDING)
+        .remove(Register::IRQ_PENDING);
+
+    // Removing the pending bit writes a `1` to it
+    assert_eq!(0b0101, bits);
+}
+
+#[test]
+fn write_one_to_set() {
+    let mut bits = 0b0001;
+    VolatileFlags::<Register>::from_mut(&mut bits)
+        .write_one_to_set(Register::START)
+        .insert(Register::START);
+
+    assert_eq!(0b1001, bits);
+
+    let mut bits = 0b1001;
+    VolatileFlags::<Register>::from_mut(&mut bits)
+        .write_one_to_set(Register::START)
+        .insert(Register::MODE);
+
+    // Leaving the start bit set writes a `0` to it
+    assert_eq!(0b0011, bits);
+}
+
+#[test]
+#[should_panic = "a bit can't be both write-1-to-clear and write-1-to-set"]
+fn write_one_to_clear_and_set() {
+    let mut bits = 0;
+    let _ = VolatileFlags::<Register>::from_mut(&mut bits)
+        .write_one_to_clear(Register::IRQ_PENDING | Register::MODE)
+        .write_one_to_set(Register::START | Register::MODE);
+}
@@ -0,0 +1,266 @@
+use crate::{parser::*, Flag, Flags, Words};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TestWords(Words<3>);
+
+impl TestWords {
+    const A: Self = TestWords(Words::bit(0));
+    const B: Self = TestWords(Words::bit(64));
+    const C: Self = TestWords(Words::bit(191));
+    const AB: Self = TestWords(Words::bit(0).union(Words::bit(64)));
+}
+
+impl Flags for TestWords {
+    const FLAGS: &'static [Flag<Self>] = &[
+        Flag::new("A", Self::A),
+        Flag::new("B", Self::B),
+        Flag::new("C", Self::C),
+        Flag::new("AB", Self::AB),
+    ];
+
+    type Bits = Words<3>;
+
+    fn bits(&self) -> Words<3> {
+        self.0
+    }
+
+    fn from_bits_retain(bits: Words<3>) -> Self {
+        TestWords(bits)
+    }
+}
+
+impl core::ops::BitOr for TestWords {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct TestWordsMacro: Words<3> {
+        const A = Words::bit(0);
+        const B = Words::bit(64);
+        const C = Words::bit(191);
+        const AB = Self::A.bits().union(Self::B.bits());
+    }
+}
+
+#[test]
+fn bit() {
+    assert_eq!([1, 0, 0], Words::<3>::bit(0).to_words());
+    assert_eq!([0, 1, 0], Words::<3>::bit(64).to_words());
+    assert_eq!([0, 0, 1 << 63], Words::<3>::bit(191).to_words());
+}
+
+#[test]
+fn ops() {
+    let a = Words::from_words([0b0011, 0, 1]);
+    let b = Words::from_words([0b0101, 1, 1]);
+
+    assert_eq!([0b0001, 0, 1], (a & b).to_words());
+    assert_eq!([0b0111, 1, 1], (a | b).to_words());
+    assert_eq!([0b0110, 1, 0], (a ^ b).to_words());
+    assert_eq!([!0b0011, !0, !1], (!a).to_words());
+
+    assert!(a < b);
+    assert!(Words::from_words([0, 0, 1]) > Words::from_words([!0, !0, 0]));
+}
+
+#[test]
+fn flags() {
+    assert_eq!([1, 1, 1 << 63], TestWords::all().bits().to_words());
+
+    assert_eq!(
+        Some(TestWords::A | TestWords::C),
+        TestWords::from_bits(Words::from_words([1, 0, 1 << 63])),
+    );
+    assert_eq!(None, TestWords::from_bits(Words::from_words([1, 2, 0])));
+    assert_eq!(
+        TestWords::A,
+        TestWords::from_bits_truncate(Words::from_words([1, 2, 0])),
+    );
+
+    assert_eq!(Some(TestWords::C), TestWords::from_name("C"));
+
+    assert_eq!(
+        vec!["A", "B", "C"],
+        TestWords::all()
+            .iter_names()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn parser() {
+    let mut s = String::new();
+    to_writer(&TestWords::all(), &mut s).unwrap();
+    assert_eq!("A | B | C", s);
+
+    let f = TestWords::A | TestWords::from_bits_retain(Words::from_words([0, 2, 1]));
+
+    s.clear();
+    to_writer(&f, &mut s).unwrap();
+    assert_eq!("A | 0x100000000000000020000000000000000", s);
+    assert_eq!(f, from_str::<TestWords>(&s).unwrap());
+
+    assert_eq!(
+        TestWords::B | TestWords::C,
+        from_str::<TestWords>("B | C").unwrap()
+    );
+    assert_eq!(
+        TestWords::C,
+        from_str::<TestWords>("0x800000000000000000000000000000000000000000000000").unwrap()
+    );
+
+    assert!(from_str::<TestWords>("0x1000000000000000000000000000000000000000000000000").is_err());
+    assert!(from_str::<TestWords>("0x").is_err());
+    assert_eq!(TestWords::A, from_str::<TestWords>("0x+1").unwrap());
+    assert_eq!(TestWords::A, from_str::<TestWords>("0b+1").unwrap());
+    assert_eq!(Words::<3>::bit(0), Words::parse_hex("+1").unwrap());
+    assert!(from_str::<TestWords>("0x+").is_err());
+    assert!(from_str::<TestWords>("0x++1").is_err());
+    assert!(from_str::<TestWords>("0x-1").is_err());
+
+    assert_eq!(
+        TestWords::A | TestWords::B,
+        from_str::<TestWords>(
+            "0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001"
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        TestWords::A | TestWords::B,
+        from_str::<TestWords>("18446744073709551617").unwrap()
+    );
+    assert!(
+        from_str::<TestWords>("6277101735386680763835789423207666416102355444464034512896")
+            .is_err()
+    );
+
+    let f = TestWords::from_bits_retain(Words::from_words([2, 2, 0]));
+    let write = |radix| {
+        let mut s = String::new();
+        to_writer_with(&f, &mut s, &ParserOptions::new().write_radix(radix)).unwrap();
+
+        s
+    };
+
+    assert_eq!(
+        "0b100000000000000000000000000000000000000000000000000000000000000010",
+        write(Radix::Binary)
+    );
+    assert_eq!("0o4000000000000000000002", write(Radix::Octal));
+    assert_eq!("36893488147419103234", write(Radix::Decimal));
+    assert_eq!("0x20000000000000002", write(Radix::Hex));
+
+    for radix in [Radix::Binary, Radix::Octal, Radix::Decimal, Radix::Hex] {
+        assert_eq!(f, from_str::<TestWords>(&write(radix)).unwrap());
+    }
+}
+
+#[test]
+fn write_decimal() {
+    let write = |words| {
+        let mut s = String::new();
+        Words::<3>::from_words(words)
+            .write_radix(&mut s, Radix::Decimal)
+            .unwrap();
+
+        s
+    };
+
+    assert_eq!("0", write([0, 0, 0]));
+    assert_eq!(
+        "10000000000000000000",
+        write([10_000_000_000_000_000_000, 0, 0])
+    );
+    assert_eq!(
+        "6277101735386680763835789423207666416102355444464034512895",
+        write([!0, !0, !0])
+    );
+}
+
+#[test]
+fn fmt() {
+    let w = Words::<2>::from_words([0xab, 1]);
+
+    assert_eq!("100000000000000ab", format!("{:x}", w));
+    assert_eq!("0x100000000000000AB", format!("{:#X}", w));
+    assert_eq!("0o2000000000000000000253", format!("{:#o}", w));
+    assert_eq!(
+        "0b1010",
+        format!("{:#b}", Words::<2>::from_words([0b1010, 0]))
+    );
+    assert_eq!("0x0", format!("{:#x}", Words::<2>::EMPTY));
+
+    assert_eq!(
+        "0x00ab",
+        format!("{:#06x}", Words::<2>::from_words([0xab, 0]))
+    );
+    assert_eq!("  ab", format!("{:>4x}", Words::<2>::from_words([0xab, 0])));
+    assert_eq!(
+        "ab**",
+        format!("{:*<4x}", Words::<2>::from_words([0xab, 0]))
+    );
+}
+
+#[test]
+fn macro_generated() {
+    const AC: TestWordsMacro = TestWordsMacro::A.union(TestWordsMacro::C);
+
+    assert_eq!([1, 0, 1 << 63], AC.bits().to_words());
+    assert_eq!([1, 1, 1 << 63], TestWordsMacro::all().bits().to_words());
+    assert!(TestWordsMacro::empty().is_empty());
+    assert!(TestWordsMacro::all().is_all());
+
+    assert!(AC.contains(TestWordsMacro::C));
+    assert!(AC.intersects(TestWordsMacro::AB));
+    assert!(!AC.contains(TestWordsMacro::AB));
+
+    assert_eq!(TestWordsMacro::C, AC - TestWordsMacro::A);
+    assert_eq!(TestWordsMacro::A, AC & TestWordsMacro::AB);
+    assert_eq!(
+        TestWordsMacro::B | TestWordsMacro::C,
+        AC ^ TestWordsMacro::AB
+    );
+    assert_eq!(TestWordsMacro::B, !AC);
+
+    assert_eq!(
+        None,
+        TestWordsMacro::from_bits(Words::from_words([1, 2, 0]))
+    );
+    assert_eq!(
+        TestWordsMacro::A,
+        TestWordsMacro::from_bits_truncate(Words::from_words([1, 2, 0])),
+    );
+    assert_eq!(Some(TestWordsMacro::C), TestWordsMacro::from_name("C"));
+
+    assert_eq!(
+        vec!["A", "C"],
+        AC.iter_names().map(|(name, _)| name).collect::<Vec<_>>(),
+    );
+
+    assert_eq!("TestWordsMacro(A | C)", format!("{:?}", AC));
+    assert_eq!(
+        "TestWordsMacro(0x0)",
+        format!("{:?}", TestWordsMacro::empty())
+    );
+    assert_eq!(
+        "TestWordsMacro(A | 0x20000000000000000)",
+        format!(
+            "{:?}",
+            TestWordsMacro::A | TestWordsMacro::from_bits_retain(Words::bit(65))
+        )
+    );
+    assert_eq!("0x10000000000000001", format!("{:#x}", TestWordsMacro::AB));
+
+    assert_eq!(AC, from_str::<TestWordsMacro>("A | C").unwrap());
+    assert_eq!(AC, crate::flags!(TestWordsMacro, "A | C"));
+    assert_eq!(
+        TestWordsMacro::AB,
+        crate::flags!(TestWordsMacro, "0x1_0000_0000_0000_0001"),
+    );
+}
@@ -0,0 +1,98 @@
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+use crate::{zerocopy as strict, Flag, Flags, Words};
+
+#[derive(IntoBytes, FromBytes, KnownLayout, Immutable, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+struct Color(u32);
+
+bitflags! {
+    impl Color: u32 {
+        const RED = 0x1;
+        const GREEN = 0x2;
+        const BLUE = 0x4;
+    }
+}
+
+// Types using `Words` implement `Flags` manually, and can derive `zerocopy` traits
+#[derive(IntoBytes, FromBytes, KnownLayout, Immutable, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+struct Wide(Words<2>);
+
+impl Flags for Wide {
+    const FLAGS: &'static [Flag<Self>] = &[
+        Flag::new("LOW", Wide(Words::bit(0))),
+        Flag::new("HIGH", Wide(Words::bit(127))),
+    ];
+
+    type Bits = Words<2>;
+
+    fn bits(&self) -> Words<2> {
+        self.0
+    }
+
+    fn from_bits_retain(bits: Words<2>) -> Self {
+        Wide(bits)
+    }
+}
+
+#[test]
+fn read_write() {
+    let color = Color::RED | Color::BLUE;
+
+    assert_eq!(&0x5u32.to_ne_bytes(), color.as_bytes());
+    assert_eq!(color, Color::read_from_bytes(color.as_bytes()).unwrap());
+
+    // Unknown bits are still read
+    assert_eq!(
+        Color::from_bits_retain(0x9),
+        Color::read_from_bytes(&0x9u32.to_ne_bytes()).unwrap()
+    );
+
+    let wide = Wide(Words::bit(0).union(Words::bit(127)));
+    assert_eq!(wide, Wide::read_from_bytes(wide.as_bytes()).unwrap());
+    assert_eq!(Some(&wide), strict::try_ref_from_bytes(wide.as_bytes()));
+
+    let wide = Wide(Words::bit(1));
+    assert_eq!(None, strict::try_ref_from_bytes::<Wide>(wide.as_bytes()));
+}
+
+#[test]
+fn try_read_from_bytes() {
+    assert_eq!(
+        Some(Color::RED | Color::BLUE),
+        strict::try_read_from_bytes(&0x5u32.to_ne_bytes())
+    );
+    assert_eq!(
+        None,
+        strict::try_read_from_bytes::<Color>(&0x9u32.to_ne_bytes())
+    );
+    assert_eq!(None, strict::try_read_from_bytes::<Color>(&[0x1]));
+}
+
+#[test]
+fn try_ref_from_bytes() {
+    let color = Color::GREEN;
+    assert_eq!(
+        Some(&color),
+        strict::try_ref_from_bytes::<Color>(color.as_bytes())
+    );
+
+    let color = Color::from_bits_retain(0x8);
+    assert_eq!(None, strict::try_ref_from_bytes::<Color>(color.as_bytes()));
+}
+
+#[test]
+fn try_ref_slice_from_bytes() {
+    let colors = [Color::RED, Color::GREEN | Color::BLUE];
+    assert_eq!(
+        Some(&colors[..]),
+        strict::try_ref_slice_from_bytes::<Color>(colors.as_bytes())
+    );
+
+    let colors = [Color::RED, Color::from_bits_retain(0x8)];
+    assert_eq!(
+        None,
+        strict::try_ref_slice_from_bytes::<Color>(colors.as_bytes())
+    );
+}
@@ -1,11 +1,13 @@
 use core::{
     fmt,
+    marker::PhantomData,
     ops::{BitAnd, BitOr, BitXor, Not},
 };
 
 use crate::{
+    bytes::{self, BytesOf, FromBytes, ToBytes},
     iter,
-    parser::{ParseError, ParseHex, WriteHex},
+    parser::{self, AsciiDisplay, ParseError, ParseHex, Radix, WriteHex},
 };
 
 /**
@@ -14,7 +16,24 @@ A defined flags value that may be named or unnamed.
 #[derive(Debug)]
 pub struct Flag<B> {
     name: &'static str,
+    display_name: &'static str,
+    aliases: &'static [&'static str],
+    deprecated_aliases: &'static [&'static str],
     value: B,
+    field: Option<FieldVariants<B>>,
+}
+
+// The variants of a field, with the type of the field erased
+struct FieldVariants<B> {
+    name_of: fn(&B) -> Option<&'static str>,
+    from_name: fn(&str, bool) -> Option<B>,
+    variant_name: fn(usize) -> Option<&'static str>,
+}
+
+impl<B> fmt::Debug for FieldVariants<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldVariants").finish()
+    }
 }
 
 impl<B> Flag<B> {
@@ -24,7 +43,49 @@ impl<B> Flag<B> {
     If `name` is non-empty then the flag is named, otherwise it's unnamed.
     */
     pub const fn new(name: &'static str, value: B) -> Self {
-        Flag { name, value }
+        Flag {
+            name,
+            display_name: name,
+            aliases: &[],
+            deprecated_aliases: &[],
+            value,
+            field: None,
+        }
+    }
+
+    /**
+    Use a different name for this flag when formatting it as text.
+
+    The display name doesn't need to be a Rust identifier, so it can be something like `exec`.
+    It can't be empty, start with a digit, or contain whitespace, `|`, `=`, or `-`, because
+    then it couldn't be parsed again. The [`bitflags`](crate::bitflags) macro checks this when
+    the flags type is defined. Flags are still parsed from their name, as well as their
+    display name.
+    */
+    pub const fn with_display_name(mut self, display_name: &'static str) -> Self {
+        self.display_name = display_name;
+        self
+    }
+
+    /**
+    Accept other names for this flag when parsing it from text.
+    */
+    pub const fn with_aliases(mut self, aliases: &'static [&'static str]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /**
+    Accept other deprecated names for this flag when parsing it from text.
+
+    Deprecated aliases are accepted by default, but may be rejected by the [`parser`](crate::parser).
+    */
+    pub const fn with_deprecated_aliases(
+        mut self,
+        deprecated_aliases: &'static [&'static str],
+    ) -> Self {
+        self.deprecated_aliases = deprecated_aliases;
+        self
     }
 
     /**
@@ -36,6 +97,29 @@ impl<B> Flag<B> {
         self.name
     }
 
+    /**
+    Get the name of this flag used when formatting it as text.
+
+    This is the same as [`Flag::name`] unless the flag was given a different display name.
+    */
+    pub const fn display_name(&self) -> &'static str {
+        self.display_name
+    }
+
+    /**
+    Get the other names accepted for this flag when parsing it from text.
+    */
+    pub const fn aliases(&self) -> &'static [&'static str] {
+        self.aliases
+    }
+
+    /**
+    Get the other deprecated names accepted for this flag when parsing it from text.
+    */
+    pub const fn deprecated_aliases(&self) -> &'static [&'static str] {
+        self.deprecated_aliases
+    }
+
     /**
     Get the flags value of this flag.
     */
@@ -60,6 +144,156 @@ impl<B> Flag<B> {
     pub const fn is_unnamed(&self) -> bool {
         self.name.is_empty()
     }
+
+    /**
+    Whether the flag is a multi-bit field.
+
+    Fields are declared with a `#[field(Type)]` attribute in the [`bitflags`](macro.bitflags.html)
+    macro. The value of a field flag is the mask of its bits.
+    */
+    pub const fn is_field(&self) -> bool {
+        self.field.is_some()
+    }
+
+    /**
+    Get the name of the variant of this field that's set in a flags value.
+
+    This method will return `None` if the flag isn't a field, or if the bits of the field
+    don't correspond to any variant.
+    */
+    pub fn field_variant(&self, flags: &B) -> Option<&'static str> {
+        self.field.as_ref().and_then(|field| (field.name_of)(flags))
+    }
+
+    /**
+    Get a flags value with the bits of the named variant of this field set.
+
+    This method will return `None` if the flag isn't a field, or if `name` doesn't
+    correspond to any variant.
+    */
+    pub fn parse_field_variant(&self, name: &str) -> Option<B> {
+        self.field
+            .as_ref()
+            .and_then(|field| (field.from_name)(name, false))
+    }
+
+    /**
+    Get the names of each variant of this field.
+
+    This method will return an empty iterator if the flag isn't a field.
+    */
+    pub fn field_variants(&self) -> impl Iterator<Item = &'static str> {
+        let variant_name = self.field.as_ref().map(|field| field.variant_name);
+        let mut index = 0;
+
+        core::iter::from_fn(move || {
+            let name = variant_name?(index)?;
+            index += 1;
+
+            Some(name)
+        })
+    }
+
+    // Like `parse_field_variant`, but compares names ignoring ASCII case
+    pub(crate) fn parse_field_variant_ignore_case(&self, name: &str) -> Option<B> {
+        self.field
+            .as_ref()
+            .and_then(|field| (field.from_name)(name, true))
+    }
+}
+
+/**
+A value of a multi-bit field, like an enum.
+
+Types implementing this trait can be used as fields in the [`bitflags`](macro.bitflags.html) macro,
+which will implement [`Field`] for them.
+
+## Implementing `FieldValue`
+
+```
+use bitflags::FieldValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Slow = 0,
+    Fast = 1,
+    Turbo = 2,
+}
+
+impl FieldValue<u8> for Mode {
+    const VARIANTS: &'static [(&'static str, Mode)] = &[
+        ("Slow", Mode::Slow),
+        ("Fast", Mode::Fast),
+        ("Turbo", Mode::Turbo),
+    ];
+
+    fn to_bits(&self) -> u8 {
+        *self as u8
+    }
+}
+```
+*/
+pub trait FieldValue<B: Bits>: Copy + 'static {
+    /// The names and values of each variant.
+    const VARIANTS: &'static [(&'static str, Self)];
+
+    /// Get the bits of this value, before they're shifted into the field.
+    fn to_bits(&self) -> B;
+
+    /// Get the variant with the given bits.
+    ///
+    /// This method will return `None` if `bits` doesn't correspond to any variant.
+    fn from_bits(bits: B) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .find(|(_, variant)| variant.to_bits() == bits)
+            .map(|(_, variant)| *variant)
+    }
+
+    /// Get the variant with the given name.
+    ///
+    /// This method will return `None` if `name` doesn't correspond to any variant.
+    fn from_name(name: &str) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .find(|(variant, _)| *variant == name)
+            .map(|(_, variant)| *variant)
+    }
+
+    /// Get the name of this value.
+    ///
+    /// This method will return `None` if this value doesn't correspond to any variant.
+    fn name(&self) -> Option<&'static str> {
+        let bits = self.to_bits();
+
+        Self::VARIANTS
+            .iter()
+            .find(|(_, variant)| variant.to_bits() == bits)
+            .map(|(name, _)| *name)
+    }
+}
+
+/**
+A multi-bit field in a flags type.
+
+This trait is implemented by the [`bitflags`](macro.bitflags.html) macro for the type of each
+flag declared with a `#[field(Type)]` attribute. Use [`Flags::get_field`] and
+[`Flags::set_field`] to access a field in a flags value.
+*/
+pub trait Field<F: Flags>: FieldValue<F::Bits> {
+    /// The name of the flag the field is declared with.
+    const NAME: &'static str;
+
+    /// The mask of bits in the field.
+    const MASK: F;
+
+    /// Get the value of the field from a flags value.
+    ///
+    /// This method will return `None` if the bits of the field don't correspond to any variant.
+    fn from_flags(flags: &F) -> Option<Self>;
+
+    /// Get a flags value with only the bits of the field set to this value.
+    fn to_flags(&self) -> F;
 }
 
 /**
@@ -136,7 +370,23 @@ pub trait Flags: Sized + 'static {
     /// The underlying bits type.
     type Bits: Bits;
 
+    /// Reserved bits that must always be unset.
+    ///
+    /// These bits are never known, even if they're in a defined flag. They're unset
+    /// by truncating operators and ignored when formatting and parsing.
+    const RESERVED_ZERO: Self::Bits = <Self::Bits as Bits>::EMPTY;
+
+    /// Reserved bits that must always be set.
+    ///
+    /// These bits are always known. They're set by truncating operators
+    /// and ignored when formatting and parsing.
+    const RESERVED_ONE: Self::Bits = <Self::Bits as Bits>::EMPTY;
+
     /// Get a flags value with all bits unset.
+    ///
+    /// This also unsets any [reserved bits that must be set](Flags::RESERVED_ONE), so the
+    /// result isn't a valid value if there are any. Use [`Flags::from_bits_truncate`] with an empty
+    /// value, or `Default` for generated flags types, to get the smallest valid value instead.
     fn empty() -> Self {
         Self::from_bits_retain(Self::Bits::EMPTY)
     }
@@ -149,7 +399,7 @@ pub trait Flags: Sized + 'static {
             truncated = truncated | flag.value().bits();
         }
 
-        Self::from_bits_retain(truncated)
+        Self::from_bits_retain((truncated & !Self::RESERVED_ZERO) | Self::RESERVED_ONE)
     }
 
     /// This method will return `true` if any unknown bits are set.
@@ -176,8 +426,10 @@ pub trait Flags: Sized + 'static {
     }
 
     /// Convert from a bits value, unsetting any unknown bits.
+    ///
+    /// Any reserved bits that must always be set will also be set.
     fn from_bits_truncate(bits: Self::Bits) -> Self {
-        Self::from_bits_retain(bits & Self::all().bits())
+        Self::from_bits_retain((bits & Self::all().bits()) | Self::RESERVED_ONE)
     }
 
     /// Convert from a bits value exactly.
@@ -186,7 +438,8 @@ pub trait Flags: Sized + 'static {
     /// Get a flags value with the bits of a flag with the given name set.
     ///
     /// This method will return `None` if `name` is empty or doesn't
-    /// correspond to any named flag.
+    /// correspond to any named flag. The name of a field returns a value
+    /// with all bits of the field set.
     fn from_name(name: &str) -> Option<Self> {
         // Don't parse empty names as empty flags
         if name.is_empty() {
@@ -202,6 +455,33 @@ pub trait Flags: Sized + 'static {
         None
     }
 
+    /// Get a flags value from a sequence of flags, like `["A", "B", "0x0c"]`.
+    ///
+    /// Each item may be a name, a field, or a number. Unknown bits will be retained.
+    /// See [`parser::from_names`] for details.
+    fn from_names<I>(names: I) -> Result<Self, ParseError>
+    where
+        Self::Bits: ParseHex,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        parser::from_names(names)
+    }
+
+    /// Get the value of a multi-bit field.
+    ///
+    /// This method will return `None` if the bits of the field don't correspond to any variant.
+    fn get_field<T: Field<Self>>(&self) -> Option<T> {
+        T::from_flags(self)
+    }
+
+    /// Set the value of a multi-bit field.
+    ///
+    /// Any bits in the field are unset before the bits of `value` are set.
+    fn set_field<T: Field<Self>>(&mut self, value: T) {
+        *self = Self::from_bits_retain((self.bits() & !T::MASK.bits()) | value.to_flags().bits());
+    }
+
     /// Yield a set of contained flags values.
     ///
     /// Each yielded flags value will correspond to a defined named flag. Any unknown bits
@@ -325,6 +605,78 @@ pub trait Flags: Sized + 'static {
     fn complement(self) -> Self {
         Self::from_bits_truncate(!self.bits())
     }
+
+    /// Encode the bits value as little-endian bytes.
+    ///
+    /// See the [`bytes`](crate::bytes) module for encoding with bit `0` as the most significant bit.
+    fn to_le_bytes(&self) -> BytesOf<Self>
+    where
+        Self::Bits: ToBytes,
+    {
+        bytes::to_le_bytes(self)
+    }
+
+    /// Encode the bits value as big-endian bytes.
+    ///
+    /// See the [`bytes`](crate::bytes) module for encoding with bit `0` as the most significant bit.
+    fn to_be_bytes(&self) -> BytesOf<Self>
+    where
+        Self::Bits: ToBytes,
+    {
+        bytes::to_be_bytes(self)
+    }
+
+    /// Decode a bits value from little-endian bytes.
+    ///
+    /// This method will return `None` if any unknown bits are set.
+    fn from_le_bytes(bytes: BytesOf<Self>) -> Option<Self>
+    where
+        Self::Bits: FromBytes,
+    {
+        bytes::from_le_bytes(bytes)
+    }
+
+    /// Decode a bits value from big-endian bytes.
+    ///
+    /// This method will return `None` if any unknown bits are set.
+    fn from_be_bytes(bytes: BytesOf<Self>) -> Option<Self>
+    where
+        Self::Bits: FromBytes,
+    {
+        bytes::from_be_bytes(bytes)
+    }
+
+    /// Decode a bits value from little-endian bytes, unsetting any unknown bits.
+    fn from_le_bytes_truncate(bytes: BytesOf<Self>) -> Self
+    where
+        Self::Bits: FromBytes,
+    {
+        bytes::from_le_bytes_truncate(bytes)
+    }
+
+    /// Decode a bits value from big-endian bytes, unsetting any unknown bits.
+    fn from_be_bytes_truncate(bytes: BytesOf<Self>) -> Self
+    where
+        Self::Bits: FromBytes,
+    {
+        bytes::from_be_bytes_truncate(bytes)
+    }
+
+    /// Decode a bits value from little-endian bytes exactly.
+    fn from_le_bytes_retain(bytes: BytesOf<Self>) -> Self
+    where
+        Self::Bits: FromBytes,
+    {
+        bytes::from_le_bytes_retain(bytes)
+    }
+
+    /// Decode a bits value from big-endian bytes exactly.
+    fn from_be_bytes_retain(bytes: BytesOf<Self>) -> Self
+    where
+        Self::Bits: FromBytes,
+    {
+        bytes::from_be_bytes_retain(bytes)
+    }
 }
 
 /**
@@ -352,6 +704,77 @@ pub trait Bits:
 // or they may fail to compile based on crate features
 pub trait Primitive {}
 
+/// Operations on the bits type of a flags type generated by the `bitflags!` macro.
+///
+/// Trait methods can't be called in `const` functions, so generated methods use these
+/// instead of operators. Each primitive bits type has its own impl.
+#[doc(hidden)]
+pub struct ConstBits<B>(PhantomData<B>);
+
+macro_rules! impl_const_bits {
+    ($($t:ty as $u:ty,)*) => {
+        $(
+            impl ConstBits<$t> {
+                #[inline]
+                pub const fn union(a: $t, b: $t) -> $t {
+                    a | b
+                }
+
+                #[inline]
+                pub const fn intersection(a: $t, b: $t) -> $t {
+                    a & b
+                }
+
+                #[inline]
+                pub const fn difference(a: $t, b: $t) -> $t {
+                    a & !b
+                }
+
+                #[inline]
+                pub const fn symmetric_difference(a: $t, b: $t) -> $t {
+                    a ^ b
+                }
+
+                #[inline]
+                pub const fn complement(a: $t) -> $t {
+                    !a
+                }
+
+                #[inline]
+                pub const fn eq(a: $t, b: $t) -> bool {
+                    a == b
+                }
+
+                #[inline]
+                pub const fn from_u128(bits: u128) -> $t {
+                    bits as $t
+                }
+
+                // Fields are shifted as unsigned values so signed bits types
+                // don't sign-extend them
+                #[inline]
+                pub const fn field_from(bits: $t, mask: $t) -> $t {
+                    ((bits & mask) as $u >> mask.trailing_zeros()) as $t
+                }
+
+                #[inline]
+                pub const fn field_to(value: $t, mask: $t) -> $t {
+                    ((value as $u) << mask.trailing_zeros()) as $t & mask
+                }
+            }
+        )*
+    };
+}
+
+impl_const_bits! {
+    u8 as u8, i8 as u8,
+    u16 as u16, i16 as u16,
+    u32 as u32, i32 as u32,
+    u64 as u64, i64 as u64,
+    u128 as u128, i128 as u128,
+    usize as usize, isize as usize,
+}
+
 macro_rules! impl_bits {
     ($($u:ty, $i:ty,)*) => {
         $(
@@ -369,24 +792,140 @@ macro_rules! impl_bits {
                 fn parse_hex(input: &str) -> Result<Self, ParseError> {
                     <$u>::from_str_radix(input, 16).map_err(|_| ParseError::invalid_hex_flag(input))
                 }
+
+                fn parse_radix(input: &str, radix: Radix) -> Result<Self, ParseError> {
+                    <$u>::parse_radix_bytes(input.as_bytes(), radix)
+                        .map_err(|_| ParseError::invalid_number_flag(input))
+                }
+
+                fn parse_radix_bytes(input: &[u8], radix: Radix) -> Result<Self, ParseError> {
+                    let invalid = || ParseError::invalid_number_flag(AsciiDisplay(input));
+
+                    let mut value: $u = 0;
+                    let mut any_digits = false;
+
+                    // A leading `+` is accepted, like `from_str_radix`
+                    let digits = input.strip_prefix(b"+").unwrap_or(input);
+
+                    // Digits may be separated by any number of `_`
+                    // Non-ASCII bytes aren't digits in any radix, so they're treated as Latin-1
+                    for c in digits.iter().filter(|b| **b != b'_').map(|b| char::from(*b)) {
+                        let digit = c.to_digit(radix.base()).ok_or_else(invalid)?;
+
+                        value = value
+                            .checked_mul(radix.base() as $u)
+                            .and_then(|value| value.checked_add(digit as $u))
+                            .ok_or_else(invalid)?;
+                        any_digits = true;
+                    }
+
+                    if !any_digits {
+                        return Err(invalid());
+                    }
+
+                    Ok(value)
+                }
             }
 
             impl ParseHex for $i {
                 fn parse_hex(input: &str) -> Result<Self, ParseError> {
                     <$i>::from_str_radix(input, 16).map_err(|_| ParseError::invalid_hex_flag(input))
                 }
+
+                fn parse_radix(input: &str, radix: Radix) -> Result<Self, ParseError> {
+                    <$i>::parse_radix_bytes(input.as_bytes(), radix)
+                        .map_err(|_| ParseError::invalid_number_flag(input))
+                }
+
+                fn parse_radix_bytes(input: &[u8], radix: Radix) -> Result<Self, ParseError> {
+                    let invalid = || ParseError::invalid_number_flag(AsciiDisplay(input));
+
+                    match input.strip_prefix(b"-") {
+                        // A leading `-` negates the number, like `from_str_radix`
+                        Some(digits) if !digits.starts_with(b"+") => {
+                            let magnitude =
+                                <$u>::parse_radix_bytes(digits, radix).map_err(|_| invalid())?;
+
+                            if magnitude <= <$i>::MIN as $u {
+                                Ok((magnitude as $i).wrapping_neg())
+                            } else {
+                                Err(invalid())
+                            }
+                        }
+                        // Otherwise signed numbers are parsed from their two's complement bits,
+                        // like they're written
+                        _ => <$u>::parse_radix_bytes(input, radix).map(|value| value as $i),
+                    }
+                }
             }
 
             impl WriteHex for $u {
                 fn write_hex<W: fmt::Write>(&self, mut writer: W) -> fmt::Result {
                     write!(writer, "{:x}", self)
                 }
+
+                fn write_radix<W: fmt::Write>(&self, mut writer: W, radix: Radix) -> fmt::Result {
+                    match radix {
+                        Radix::Binary => write!(writer, "0b{:b}", self),
+                        Radix::Octal => write!(writer, "0o{:o}", self),
+                        Radix::Decimal => write!(writer, "{}", self),
+                        Radix::Hex => write!(writer, "0x{:x}", self),
+                    }
+                }
             }
 
             impl WriteHex for $i {
                 fn write_hex<W: fmt::Write>(&self, mut writer: W) -> fmt::Result {
                     write!(writer, "{:x}", self)
                 }
+
+                fn write_radix<W: fmt::Write>(&self, writer: W, radix: Radix) -> fmt::Result {
+                    (*self as $u).write_radix(writer, radix)
+                }
+            }
+
+            impl ToBytes for $u {
+                type Bytes = [u8; core::mem::size_of::<$u>()];
+
+                fn to_le_bytes(&self) -> Self::Bytes {
+                    <$u>::to_le_bytes(*self)
+                }
+
+                fn to_be_bytes(&self) -> Self::Bytes {
+                    <$u>::to_be_bytes(*self)
+                }
+            }
+
+            impl ToBytes for $i {
+                type Bytes = [u8; core::mem::size_of::<$i>()];
+
+                fn to_le_bytes(&self) -> Self::Bytes {
+                    <$i>::to_le_bytes(*self)
+                }
+
+                fn to_be_bytes(&self) -> Self::Bytes {
+                    <$i>::to_be_bytes(*self)
+                }
+            }
+
+            impl FromBytes for $u {
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$u>::from_le_bytes(bytes)
+                }
+
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$u>::from_be_bytes(bytes)
+                }
+            }
+
+            impl FromBytes for $i {
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$i>::from_le_bytes(bytes)
+                }
+
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$i>::from_be_bytes(bytes)
+                }
             }
 
             impl Primitive for $i {}
@@ -439,6 +978,103 @@ impl<B: Flags> ImplementedByBitFlagsMacro for B {}
 #[doc(hidden)]
 pub trait ImplementedByBitFlagsMacro {}
 
+/// A flag for a multi-bit field, generated by the `bitflags!` macro.
+#[doc(hidden)]
+pub struct FieldFlag<F, T>(PhantomData<(F, T)>);
+
+impl<F: Flags, T: Field<F>> FieldFlag<F, T> {
+    pub const FLAG: Flag<F> = Flag {
+        name: T::NAME,
+        display_name: T::NAME,
+        aliases: &[],
+        deprecated_aliases: &[],
+        value: T::MASK,
+        field: Some(FieldVariants {
+            name_of: Self::name_of,
+            from_name: Self::from_name,
+            variant_name: Self::variant_name,
+        }),
+    };
+
+    fn name_of(flags: &F) -> Option<&'static str> {
+        T::from_flags(flags).and_then(|value| value.name())
+    }
+
+    fn from_name(name: &str, ignore_case: bool) -> Option<F> {
+        if ignore_case {
+            T::VARIANTS
+                .iter()
+                .find(|(variant, _)| variant.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.to_flags())
+        } else {
+            T::from_name(name).map(|value| value.to_flags())
+        }
+    }
+
+    fn variant_name(index: usize) -> Option<&'static str> {
+        T::VARIANTS.get(index).map(|(name, _)| *name)
+    }
+}
+
+/**
+Whether a display name can be parsed from text again.
+
+Display names can't be empty, start with a digit, or contain whitespace, `|`, or `=`,
+because these are part of the text grammar.
+*/
+pub const fn is_valid_display_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+
+    if bytes.is_empty() || bytes[0].is_ascii_digit() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        // Decode the next `char` from its UTF-8 encoding
+        let (c, len) = match bytes[i] {
+            b if b < 0x80 => (b as u32, 1),
+            b if b < 0xe0 => ((b as u32 & 0x1f) << 6 | (bytes[i + 1] as u32 & 0x3f), 2),
+            b if b < 0xf0 => (
+                (b as u32 & 0x0f) << 12
+                    | (bytes[i + 1] as u32 & 0x3f) << 6
+                    | (bytes[i + 2] as u32 & 0x3f),
+                3,
+            ),
+            // Four-byte `char`s are never whitespace
+            _ => (0x10000, 4),
+        };
+
+        if is_whitespace(c) || c == '|' as u32 || c == '=' as u32 {
+            return false;
+        }
+
+        i += len;
+    }
+
+    true
+}
+
+// Whether a `char` is whitespace, like `char::is_whitespace`
+const fn is_whitespace(c: u32) -> bool {
+    matches!(
+        c,
+        0x09..=0x0d
+            | 0x20
+            | 0x85
+            | 0xa0
+            | 0x1680
+            | 0x2000..=0x200a
+            | 0x2028
+            | 0x2029
+            | 0x202f
+            | 0x205f
+            | 0x3000
+    )
+}
+
 pub(crate) mod __private {
-    pub use super::{ImplementedByBitFlagsMacro, PublicFlags};
+    pub use super::{
+        is_valid_display_name, ConstBits, FieldFlag, ImplementedByBitFlagsMacro, PublicFlags,
+    };
 }
@@ -0,0 +1,185 @@
+/*!
+Access flags values in memory-mapped registers.
+
+The [`VolatileFlags`] type wraps a pointer to a register holding a flags type's bits, and
+only accesses it through volatile reads and writes:
+
+```
+use bitflags::{bitflags, volatile::VolatileFlags};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(transparent)]
+    struct Status: u32 {
+        const ENABLE = 1;
+        const IRQ_PENDING = 1 << 1;
+    }
+}
+
+// In a driver this would be a pointer to the register
+let mut register = 0b10;
+let mut status =
+    VolatileFlags::<Status>::from_mut(&mut register).write_one_to_clear(Status::IRQ_PENDING);
+
+// Enable the device and acknowledge the pending interrupt
+status.modify(|status| {
+    status.insert(Status::ENABLE);
+    status.remove(Status::IRQ_PENDING);
+});
+
+// A `1` was written to `IRQ_PENDING` to clear it
+assert_eq!(0b11, register);
+```
+
+## Write-1-to-clear and write-1-to-set bits
+
+Some registers don't store the bits written to them directly. Bits that are cleared by
+writing a `1` can be declared with [`VolatileFlags::write_one_to_clear`], and bits that are
+set by writing a `1` with [`VolatileFlags::write_one_to_set`]. Writing a `0` to either kind
+of bit has no effect.
+
+[`VolatileFlags::modify`] and the methods built on it take these bits into account, so a
+closure can insert and remove flags as usual and the right bits are written back.
+[`VolatileFlags::write`] always writes its bits exactly.
+
+A bit can't be both write-1-to-clear and write-1-to-set. Declaring it as both panics.
+
+This module requires the `volatile` feature.
+*/
+
+#![allow(unsafe_code)]
+
+use core::{fmt, marker::PhantomData, ptr};
+
+use crate::Flags;
+
+/**
+A flags value in a memory-mapped register.
+
+See the [module docs](index.html) for details.
+*/
+pub struct VolatileFlags<'a, F: Flags> {
+    ptr: *mut F::Bits,
+    write_one_to_clear: F::Bits,
+    write_one_to_set: F::Bits,
+    _marker: PhantomData<&'a mut F::Bits>,
+}
+
+impl<'a, F: Flags> VolatileFlags<'a, F> {
+    /**
+    Wrap a pointer to a register.
+
+    # Safety
+
+    `ptr` must be non-null, aligned, and valid for volatile reads and writes of `F::Bits`
+    for as long as the returned value is used.
+    */
+    pub unsafe fn new(ptr: *mut F::Bits) -> Self {
+        VolatileFlags {
+            ptr,
+            write_one_to_clear: <F::Bits as crate::Bits>::EMPTY,
+            write_one_to_set: <F::Bits as crate::Bits>::EMPTY,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Wrap a reference to some bits in regular memory.
+    ///
+    /// This method is useful for testing code that accesses registers.
+    pub fn from_mut(bits: &'a mut F::Bits) -> Self {
+        // SAFETY: References are always non-null, aligned, and valid for reads and writes
+        unsafe { VolatileFlags::new(bits) }
+    }
+
+    /// Declare bits that are cleared by writing a `1` to them.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if any of the bits were already declared as write-1-to-set.
+    #[must_use]
+    pub fn write_one_to_clear(mut self, flags: F) -> Self {
+        assert!(
+            self.write_one_to_set & flags.bits() == <F::Bits as crate::Bits>::EMPTY,
+            "a bit can't be both write-1-to-clear and write-1-to-set"
+        );
+
+        self.write_one_to_clear = self.write_one_to_clear | flags.bits();
+        self
+    }
+
+    /// Declare bits that are set by writing a `1` to them.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if any of the bits were already declared as write-1-to-clear.
+    #[must_use]
+    pub fn write_one_to_set(mut self, flags: F) -> Self {
+        assert!(
+            self.write_one_to_clear & flags.bits() == <F::Bits as crate::Bits>::EMPTY,
+            "a bit can't be both write-1-to-clear and write-1-to-set"
+        );
+
+        self.write_one_to_set = self.write_one_to_set | flags.bits();
+        self
+    }
+
+    /// Get the pointer to the register.
+    pub fn as_ptr(&self) -> *mut F::Bits {
+        self.ptr
+    }
+
+    /// Read the flags value from the register.
+    pub fn read(&self) -> F {
+        // SAFETY: The pointer is valid for volatile reads
+        F::from_bits_retain(unsafe { ptr::read_volatile(self.ptr) })
+    }
+
+    /// Write a flags value to the register.
+    ///
+    /// The bits of the flags value are written exactly, including any
+    /// write-1-to-clear and write-1-to-set bits.
+    pub fn write(&mut self, flags: F) {
+        // SAFETY: The pointer is valid for volatile writes
+        unsafe { ptr::write_volatile(self.ptr, flags.bits()) }
+    }
+
+    /// Read the flags value from the register, modify it, and write it back.
+    ///
+    /// Write-1-to-clear bits that were removed, and write-1-to-set bits that were inserted,
+    /// are written as `1`. Any other write-1-to-clear and write-1-to-set bits are written as `0`.
+    pub fn modify(&mut self, f: impl FnOnce(&mut F)) {
+        let current = self.read().bits();
+
+        let mut flags = F::from_bits_retain(current);
+        f(&mut flags);
+        let new = flags.bits();
+
+        let special = self.write_one_to_clear | self.write_one_to_set;
+
+        let bits = (new & !special)
+            | (current & !new & self.write_one_to_clear)
+            | (!current & new & self.write_one_to_set);
+
+        self.write(F::from_bits_retain(bits));
+    }
+
+    /// Insert flags into the register's value.
+    ///
+    /// This is a shorthand for calling [`Flags::insert`] in [`VolatileFlags::modify`].
+    pub fn insert(&mut self, flags: F) {
+        self.modify(|f| f.insert(flags))
+    }
+
+    /// Remove flags from the register's value.
+    ///
+    /// This is a shorthand for calling [`Flags::remove`] in [`VolatileFlags::modify`].
+    pub fn remove(&mut self, flags: F) {
+        self.modify(|f| f.remove(flags))
+    }
+}
+
+impl<'a, F: Flags + fmt::Debug> fmt::Debug for VolatileFlags<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VolatileFlags").field(&self.ptr).finish()
+    }
+}
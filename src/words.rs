@@ -0,0 +1,730 @@
+/*!
+Multi-word storage for flags types with more bits than the largest primitive integer.
+*/
+
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+    ops::{BitAnd, BitOr, BitXor, Not},
+};
+
+use crate::{
+    bytes::{FromBytes, ToBytes},
+    parser::{AsciiDisplay, ParseError, ParseHex, Radix, WriteHex},
+    traits::{ConstBits, Primitive},
+    Bits,
+};
+
+/**
+A bits type backed by an array of `N` `u64` words.
+
+Bit `n` is stored in word `n / 64` at position `n % 64`, so the first word holds the least
+significant bits. A `Words<N>` value has `N * 64` bits.
+
+## Using `Words` in flags types
+
+`Words` can be used as the bits type in the [`bitflags`](macro.bitflags.html) macro, like any
+primitive integer. The built-in operators can't be used in `const` contexts, so flags are
+defined using the `const` methods on `Words` instead:
+
+```
+use bitflags::{bitflags, Words};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Capabilities: Words<3> {
+        const READ = Words::bit(0);
+        const WRITE = Words::bit(1);
+        const AUDIT = Words::bit(150);
+
+        const READ_WRITE = Self::READ.bits().union(Self::WRITE.bits());
+    }
+}
+
+let caps = Capabilities::READ | Capabilities::AUDIT;
+
+assert!(caps.contains(Capabilities::AUDIT));
+assert_eq!("Capabilities(READ | AUDIT)", format!("{:?}", caps));
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::IntoBytes,
+        zerocopy::FromBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
+#[repr(transparent)]
+pub struct Words<const N: usize>([u64; N]);
+
+impl<const N: usize> Words<N> {
+    /// The number of bits in a single word.
+    pub const WORD_BITS: usize = 64;
+
+    /// A value with all bits unset.
+    pub const EMPTY: Self = Words([0; N]);
+
+    /// A value with all bits set.
+    pub const ALL: Self = Words([u64::MAX; N]);
+
+    /// Create a value from its words, least significant word first.
+    #[inline]
+    pub const fn from_words(words: [u64; N]) -> Self {
+        Words(words)
+    }
+
+    /// Get the words in this value, least significant word first.
+    #[inline]
+    pub const fn to_words(self) -> [u64; N] {
+        self.0
+    }
+
+    /// Get a value with only the bit at index `n` set.
+    ///
+    /// This method will panic if `n` is not less than `N * 64`.
+    #[inline]
+    pub const fn bit(n: usize) -> Self {
+        let mut words = [0; N];
+        words[n / Self::WORD_BITS] = 1 << (n % Self::WORD_BITS);
+
+        Words(words)
+    }
+
+    /// The bitwise or (`|`) of the bits in two values.
+    ///
+    /// This method is `const`, so it can be used to define composite flags.
+    #[inline]
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+
+        while i < N {
+            words[i] |= other.0[i];
+            i += 1;
+        }
+
+        Words(words)
+    }
+
+    /// The bitwise and (`&`) of the bits in two values.
+    ///
+    /// This method is `const`, so it can be used to define composite flags.
+    #[inline]
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+
+        while i < N {
+            words[i] &= other.0[i];
+            i += 1;
+        }
+
+        Words(words)
+    }
+
+    /// The bitwise exclusive-or (`^`) of the bits in two values.
+    #[inline]
+    #[must_use]
+    pub const fn symmetric_difference(self, other: Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+
+        while i < N {
+            words[i] ^= other.0[i];
+            i += 1;
+        }
+
+        Words(words)
+    }
+
+    /// The bitwise negation (`!`) of the bits in a value.
+    #[inline]
+    #[must_use]
+    pub const fn complement(self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+
+        while i < N {
+            words[i] = !words[i];
+            i += 1;
+        }
+
+        Words(words)
+    }
+
+    /// Whether all bits in this value are unset.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        let mut i = 0;
+
+        while i < N {
+            if self.0[i] != 0 {
+                return false;
+            }
+
+            i += 1;
+        }
+
+        true
+    }
+}
+
+impl<const N: usize> Words<N> {
+    // The number of unset bits below the lowest set bit, or `N * 64` if no bits are set
+    const fn trailing_zeros(&self) -> usize {
+        let mut i = 0;
+        while i < N {
+            if self.0[i] != 0 {
+                return i * 64 + self.0[i].trailing_zeros() as usize;
+            }
+
+            i += 1;
+        }
+
+        N * 64
+    }
+
+    // Shift all bits towards the least significant by `n`
+    const fn shr(self, n: usize) -> Self {
+        let (skip, shift) = (n / 64, n % 64);
+
+        let mut words = [0; N];
+        let mut i = 0;
+        while i + skip < N {
+            words[i] = self.0[i + skip] >> shift;
+
+            if shift > 0 && i + skip + 1 < N {
+                words[i] |= self.0[i + skip + 1] << (64 - shift);
+            }
+
+            i += 1;
+        }
+
+        Words(words)
+    }
+
+    // Shift all bits towards the most significant by `n`
+    const fn shl(self, n: usize) -> Self {
+        let (skip, shift) = (n / 64, n % 64);
+
+        let mut words = [0; N];
+        let mut i = skip;
+        while i < N {
+            words[i] = self.0[i - skip] << shift;
+
+            if shift > 0 && i > skip {
+                words[i] |= self.0[i - skip - 1] >> (64 - shift);
+            }
+
+            i += 1;
+        }
+
+        Words(words)
+    }
+
+    // The number of bits up to and including the most significant set bit
+    fn significant_bits(&self) -> usize {
+        for (i, word) in self.0.iter().enumerate().rev() {
+            if *word != 0 {
+                return i * Self::WORD_BITS + (Self::WORD_BITS - word.leading_zeros() as usize);
+            }
+        }
+
+        0
+    }
+
+    // The number of digits needed to write this value in a radix of `2^shift`
+    fn digits(&self, shift: usize) -> usize {
+        ((self.significant_bits() + shift - 1) / shift).max(1)
+    }
+
+    // The digit at `index` when written in a radix of `2^shift`, least significant first
+    fn digit(&self, index: usize, shift: usize) -> u32 {
+        let mut digit = 0;
+
+        for bit in (index * shift..(index + 1) * shift).rev() {
+            let set = bit < N * Self::WORD_BITS
+                && self.0[bit / Self::WORD_BITS] & (1 << (bit % Self::WORD_BITS)) != 0;
+
+            digit = (digit << 1) | set as u32;
+        }
+
+        digit
+    }
+
+    // Write this value in a radix of `2^shift`, without a prefix
+    fn write_pow2<W: fmt::Write>(&self, writer: &mut W, shift: usize, upper: bool) -> fmt::Result {
+        for index in (0..self.digits(shift)).rev() {
+            let digit = char::from_digit(self.digit(index, shift), 1 << shift).ok_or(fmt::Error)?;
+
+            writer.write_char(if upper {
+                digit.to_ascii_uppercase()
+            } else {
+                digit
+            })?;
+        }
+
+        Ok(())
+    }
+
+    // Divide this value by `divisor`, returning the quotient and remainder
+    fn div_rem(self, divisor: u64) -> (Self, u64) {
+        let mut words = self.0;
+        let mut rem = 0u128;
+
+        for word in words.iter_mut().rev() {
+            let n = (rem << Self::WORD_BITS) | u128::from(*word);
+
+            *word = (n / u128::from(divisor)) as u64;
+            rem = n % u128::from(divisor);
+        }
+
+        (Words(words), rem as u64)
+    }
+
+    // Write this value in decimal, 19 digits at a time, which is the most that fit in a `u64`
+    fn write_decimal<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let (quotient, rem) = self.div_rem(CHUNK);
+
+        if quotient.is_empty() {
+            write!(writer, "{}", rem)
+        } else {
+            quotient.write_decimal(writer)?;
+            write!(writer, "{:019}", rem)
+        }
+    }
+
+    // Write this value in a radix of `2^shift` using the formatter's width, fill, and alignment,
+    // like the formatting traits on primitive integers
+    fn fmt_pow2(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        shift: usize,
+        prefix: &str,
+        upper: bool,
+    ) -> fmt::Result {
+        let prefix = if f.alternate() { prefix } else { "" };
+        let padding = f
+            .width()
+            .unwrap_or(0)
+            .saturating_sub(prefix.len() + self.digits(shift));
+
+        if f.sign_aware_zero_pad() {
+            f.write_str(prefix)?;
+            for _ in 0..padding {
+                f.write_char('0')?;
+            }
+
+            return self.write_pow2(f, shift, upper);
+        }
+
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (padding, 0),
+        };
+
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+
+        f.write_str(prefix)?;
+        self.write_pow2(f, shift, upper)?;
+
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for Words<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<const N: usize> fmt::Debug for Words<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Words(0x")?;
+        self.write_hex(&mut *f)?;
+        f.write_str(")")
+    }
+}
+
+impl<const N: usize> fmt::Binary for Words<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_pow2(f, 1, "0b", false)
+    }
+}
+
+impl<const N: usize> fmt::Octal for Words<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_pow2(f, 3, "0o", false)
+    }
+}
+
+impl<const N: usize> fmt::LowerHex for Words<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_pow2(f, 4, "0x", false)
+    }
+}
+
+impl<const N: usize> fmt::UpperHex for Words<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_pow2(f, 4, "0x", true)
+    }
+}
+
+impl<const N: usize> PartialOrd for Words<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for Words<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare numerically, starting from the most significant word
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> BitAnd for Words<N> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl<const N: usize> BitOr for Words<N> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl<const N: usize> BitXor for Words<N> {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        self.symmetric_difference(other)
+    }
+}
+
+impl<const N: usize> Not for Words<N> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl<const N: usize> Bits for Words<N> {
+    const EMPTY: Self = Words::EMPTY;
+
+    const ALL: Self = Words::ALL;
+}
+
+impl<const N: usize> Primitive for Words<N> {}
+
+impl<const N: usize> ConstBits<Words<N>> {
+    #[inline]
+    pub const fn union(a: Words<N>, b: Words<N>) -> Words<N> {
+        a.union(b)
+    }
+
+    #[inline]
+    pub const fn intersection(a: Words<N>, b: Words<N>) -> Words<N> {
+        a.intersection(b)
+    }
+
+    #[inline]
+    pub const fn difference(a: Words<N>, b: Words<N>) -> Words<N> {
+        a.intersection(b.complement())
+    }
+
+    #[inline]
+    pub const fn symmetric_difference(a: Words<N>, b: Words<N>) -> Words<N> {
+        a.symmetric_difference(b)
+    }
+
+    #[inline]
+    pub const fn complement(a: Words<N>) -> Words<N> {
+        a.complement()
+    }
+
+    #[inline]
+    pub const fn eq(a: Words<N>, b: Words<N>) -> bool {
+        a.symmetric_difference(b).is_empty()
+    }
+
+    // Numbers in the `flags!` macro are at most 128 bits
+    #[inline]
+    pub const fn from_u128(bits: u128) -> Words<N> {
+        let mut words = [0; N];
+
+        if N > 0 {
+            words[0] = bits as u64;
+        }
+        if N > 1 {
+            words[1] = (bits >> 64) as u64;
+        }
+
+        Words(words)
+    }
+
+    #[inline]
+    pub const fn field_from(bits: Words<N>, mask: Words<N>) -> Words<N> {
+        bits.intersection(mask).shr(mask.trailing_zeros())
+    }
+
+    #[inline]
+    pub const fn field_to(value: Words<N>, mask: Words<N>) -> Words<N> {
+        value.shl(mask.trailing_zeros()).intersection(mask)
+    }
+}
+
+// Byte arrays can't be sized by `N * 8` on stable, so `ToBytes` is implemented
+// for a fixed set of sizes
+macro_rules! impl_bytes {
+    ($($n:literal,)*) => {
+        $(
+            impl ToBytes for Words<$n> {
+                type Bytes = [u8; $n * 8];
+
+                fn to_le_bytes(&self) -> Self::Bytes {
+                    let mut bytes = [0; $n * 8];
+
+                    for (chunk, word) in bytes.chunks_exact_mut(8).zip(self.0.iter()) {
+                        chunk.copy_from_slice(&word.to_le_bytes());
+                    }
+
+                    bytes
+                }
+
+                fn to_be_bytes(&self) -> Self::Bytes {
+                    let mut bytes = [0; $n * 8];
+
+                    for (chunk, word) in bytes.chunks_exact_mut(8).zip(self.0.iter().rev()) {
+                        chunk.copy_from_slice(&word.to_be_bytes());
+                    }
+
+                    bytes
+                }
+            }
+
+            impl FromBytes for Words<$n> {
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    let mut words = [0; $n];
+
+                    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(8)) {
+                        *word = u64::from_le_bytes(word_bytes(chunk));
+                    }
+
+                    Words(words)
+                }
+
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    let mut words = [0; $n];
+
+                    for (word, chunk) in words.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+                        *word = u64::from_be_bytes(word_bytes(chunk));
+                    }
+
+                    Words(words)
+                }
+            }
+        )*
+    };
+}
+
+impl_bytes! {
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+}
+
+fn word_bytes(chunk: &[u8]) -> [u8; 8] {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(chunk);
+
+    bytes
+}
+
+impl<const N: usize> From<[u64; N]> for Words<N> {
+    #[inline]
+    fn from(words: [u64; N]) -> Self {
+        Words(words)
+    }
+}
+
+impl<const N: usize> From<Words<N>> for [u64; N] {
+    #[inline]
+    fn from(words: Words<N>) -> Self {
+        words.0
+    }
+}
+
+impl<const N: usize> ParseHex for Words<N> {
+    fn parse_hex(input: &str) -> Result<Self, ParseError> {
+        // Each word is 16 hex digits
+        const DIGITS: usize = 16;
+
+        // A leading `+` is accepted, like the primitive bits types
+        let digits = input.strip_prefix('+').unwrap_or(input);
+
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseError::invalid_hex_flag(input));
+        }
+
+        // Leading zeros don't count towards the number of bits
+        let significant = digits.trim_start_matches('0');
+        if significant.len() > N * DIGITS {
+            return Err(ParseError::invalid_hex_flag(input));
+        }
+
+        let mut words = [0; N];
+        let mut end = significant.len();
+        for word in words.iter_mut() {
+            if end == 0 {
+                break;
+            }
+
+            let start = end.saturating_sub(DIGITS);
+            *word = u64::from_str_radix(&significant[start..end], 16)
+                .map_err(|_| ParseError::invalid_hex_flag(input))?;
+            end = start;
+        }
+
+        Ok(Words(words))
+    }
+
+    fn parse_radix(input: &str, radix: Radix) -> Result<Self, ParseError> {
+        Self::parse_radix_bytes(input.as_bytes(), radix)
+            .map_err(|_| ParseError::invalid_number_flag(input))
+    }
+
+    fn parse_radix_bytes(input: &[u8], radix: Radix) -> Result<Self, ParseError> {
+        let invalid = || ParseError::invalid_number_flag(AsciiDisplay(input));
+
+        let mut words = [0u64; N];
+        let mut any_digits = false;
+
+        // A leading `+` is accepted, like the primitive bits types
+        let digits = input.strip_prefix(b"+").unwrap_or(input);
+
+        // Digits may be separated by any number of `_`
+        // Non-ASCII bytes aren't digits in any radix, so they're treated as Latin-1
+        for c in digits
+            .iter()
+            .filter(|b| **b != b'_')
+            .map(|b| char::from(*b))
+        {
+            let mut carry = u128::from(c.to_digit(radix.base()).ok_or_else(invalid)?);
+
+            // Multiply by the base and add the digit, starting from the least significant word
+            for word in words.iter_mut() {
+                let next = u128::from(*word) * u128::from(radix.base()) + carry;
+
+                *word = next as u64;
+                carry = next >> Words::<N>::WORD_BITS;
+            }
+
+            if carry != 0 {
+                return Err(invalid());
+            }
+
+            any_digits = true;
+        }
+
+        if !any_digits {
+            return Err(invalid());
+        }
+
+        Ok(Words(words))
+    }
+}
+
+impl<const N: usize> WriteHex for Words<N> {
+    fn write_hex<W: fmt::Write>(&self, mut writer: W) -> fmt::Result {
+        let mut words = self.0.iter().rev().skip_while(|word| **word == 0);
+
+        // The most significant non-zero word is written without padding
+        match words.next() {
+            Some(word) => write!(writer, "{:x}", word)?,
+            None => return writer.write_str("0"),
+        }
+
+        for word in words {
+            write!(writer, "{:016x}", word)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_radix<W: fmt::Write>(&self, mut writer: W, radix: Radix) -> fmt::Result {
+        writer.write_str(radix.prefix())?;
+
+        match radix {
+            Radix::Binary => self.write_pow2(&mut writer, 1, false),
+            Radix::Octal => self.write_pow2(&mut writer, 3, false),
+            Radix::Decimal => self.write_decimal(&mut writer),
+            Radix::Hex => self.write_hex(writer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for Words<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for word in self.0.iter() {
+            tuple.serialize_element(word)?;
+        }
+
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for Words<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct WordsVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for WordsVisitor<N> {
+            type Value = Words<N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "a sequence of {} `u64` words", N)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut words = [0; N];
+                for (i, word) in words.iter_mut().enumerate() {
+                    *word = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+                }
+
+                Ok(Words(words))
+            }
+        }
+
+        deserializer.deserialize_tuple(N, WordsVisitor)
+    }
+}
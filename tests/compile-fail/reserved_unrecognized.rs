@@ -0,0 +1,12 @@
+use bitflags::bitflags;
+
+bitflags! {
+    pub struct Flags: u8 {
+        const A = 1;
+
+        #[reserved(bogus)]
+        const _ = 1 << 7;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,67 @@
+//! Tests for deriving `JsonSchema` on flags types, including the examples in the docs of the
+//! `bitflags::schemars` module.
+//!
+//! These are in a separate crate so only this crate needs the `derive` feature of `schemars`,
+//! which would otherwise raise the Rust version needed to test `bitflags`.
+
+#![cfg(test)]
+
+use bitflags::bitflags;
+use schemars::{generate::SchemaSettings, JsonSchema, SchemaGenerator};
+
+bitflags! {
+    #[derive(JsonSchema)]
+    #[schemars(transparent)]
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+    }
+}
+
+#[test]
+fn derive() {
+    let schema = schemars::schema_for!(Permissions);
+
+    assert_eq!(
+        Some("string"),
+        schema.get("type").and_then(|ty| ty.as_str())
+    );
+    assert_eq!(
+        Some("Permissions"),
+        schema.get("title").and_then(|title| title.as_str())
+    );
+    assert_eq!(
+        bitflags::schemars::schema::<Permissions>(&mut generator()).get("pattern"),
+        schema.get("pattern")
+    );
+}
+
+#[test]
+fn schema_with() {
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct File {
+        #[schemars(schema_with = "bitflags::schemars::seq_strict_schema::<Permissions>")]
+        permissions: Permissions,
+    }
+
+    let schema = schemars::schema_for!(File);
+
+    assert_eq!(
+        Some(
+            &schemars::json_schema!({
+                "type": "array",
+                "items": {
+                    "type": "string",
+                    "enum": ["READ", "WRITE"],
+                },
+            })
+            .to_value()
+        ),
+        schema.pointer("/properties/permissions")
+    );
+}
+
+fn generator() -> SchemaGenerator {
+    SchemaGenerator::new(SchemaSettings::default())
+}